@@ -0,0 +1,55 @@
+// Generates the registry of the days, from the `src/dayNN.rs` files
+//
+// NOTE: Each `src/dayNN.rs` file must define a `DayNN` struct implementing `Solution`, like the
+//       `src/day_example.rs` template does.
+
+use std::path::Path;
+use std::{env, fs};
+
+/// Whether the file name is like `day01.rs` (from day01 to day25), gives the day name if so.
+fn day_name(file_name: &str) -> Option<&str> {
+    let name = file_name.strip_suffix(".rs")?;
+    let number = name.strip_prefix("day")?;
+    if number.len() != 2 || !number.chars().all(|chr| chr.is_ascii_digit()) {
+        return None;
+    }
+    // NOTE: Same days as `scaffold::day_number`, from day01 to day25.
+    let number: u8 = number.parse().ok()?;
+    (1..=25).contains(&number).then_some(name)
+}
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let src_dir = Path::new(&manifest_dir).join("src");
+    // NOTE: For a directory, cargo checks all the files in it, so a new day is always seen.
+    println!("cargo:rerun-if-changed=src");
+
+    let mut day_names: Vec<String> = fs::read_dir(&src_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let file_name = entry.ok()?.file_name().into_string().ok()?;
+            day_name(&file_name).map(str::to_string)
+        })
+        .collect();
+    day_names.sort();
+
+    // NOTE: The generated file is in OUT_DIR, so the modules need the absolute path of their file.
+    let mut code = String::new();
+    for name in &day_names {
+        let path = src_dir.join(format!("{}.rs", name));
+        code += &format!(
+            "#[path = {:?}]\npub mod {};\n",
+            path.display().to_string(),
+            name
+        );
+    }
+    code += "\n/// Registry of all the days, in order.\n";
+    code += "pub static DAYS: &[&dyn Day] = &[\n";
+    for name in &day_names {
+        code += &format!("    &{}::Day{},\n", name, &name["day".len()..]);
+    }
+    code += "];\n";
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("days.rs");
+    fs::write(out_path, code).unwrap();
+}
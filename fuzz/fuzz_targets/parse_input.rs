@@ -0,0 +1,17 @@
+#![no_main]
+
+use adventofcode_2021::DAYS;
+use libfuzzer_sys::fuzz_target;
+
+// Any input of any day must parse or give a parse error, never a panic.
+//
+// NOTE: The first byte picks the day in `DAYS`, so a new day is fuzzed without a new target.
+fuzz_target!(|data: &[u8]| {
+    let Some((&day_idx, rest)) = data.split_first() else {
+        return;
+    };
+    let day = DAYS[day_idx as usize % DAYS.len()];
+    if let Ok(raw_input) = std::str::from_utf8(rest) {
+        let _ = day.parse(raw_input);
+    }
+});
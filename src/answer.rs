@@ -0,0 +1,200 @@
+// Answer of a day's part, which is not always a small number
+
+use std::fmt;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Answer of a part.
+///
+/// NOTE: An integer is always an `Int` when it fits in 64 bits and a `Big` otherwise, so the same
+///       number is always the same variant. The answers are given to the website as text, so a
+///       `Str` or a `Text` is equal to any answer displayed the same way (e.g. `Int(42)` and
+///       `Str("42")` are equal).
+#[derive(Debug, Clone, Eq)]
+pub enum Answer {
+    Int(i64),
+    /// An integer too big (or too small) for an `Int`, of any size, in decimal without leading
+    /// zeros (see `Answer::from_text`).
+    Big(String),
+    /// A single line of text.
+    Str(String),
+    /// Multiple lines of text, like letters rendered in a grid.
+    Text(String),
+}
+
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Text(_))
+    }
+
+    /// Reads an answer from text, an integer (like `-0042`) gives an `Int` (or a `Big`).
+    pub fn from_text(text: &str) -> Self {
+        Self::parse_integer(text).unwrap_or_else(|| Answer::from(text.to_string()))
+    }
+
+    fn parse_integer(text: &str) -> Option<Self> {
+        let (sign, digits) = match text.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", text.strip_prefix('+').unwrap_or(text)),
+        };
+        if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+        if let Ok(value) = text.parse::<i64>() {
+            return Some(Answer::Int(value));
+        }
+        // NOTE: It doesn't fit in an i64, so it is not zero & has digits after the leading zeros.
+        let digits = digits.trim_start_matches('0');
+        Some(Answer::Big(format!("{}{}", sign, digits)))
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Int(a), Answer::Int(b)) => a == b,
+            (Answer::Big(a), Answer::Big(b)) => a == b,
+            // NOTE: An integer is always in the same variant, an `Int` is never equal to a `Big`.
+            (Answer::Int(_), Answer::Big(_)) | (Answer::Big(_), Answer::Int(_)) => false,
+            // Only the text answers are compared as text
+            (a, b) => a.to_string() == b.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(value) => write!(f, "{}", value),
+            Answer::Big(text) | Answer::Str(text) | Answer::Text(text) => {
+                write!(f, "{}", text)
+            }
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Int(value)
+    }
+}
+
+// NOTE: A macro, to convert from all the other integer types the same way.
+macro_rules! answer_from_int {
+    ($($int: ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(value: $int) -> Self {
+                    match i64::try_from(value) {
+                        Ok(value) => Answer::Int(value),
+                        Err(_) => Answer::Big(value.to_string()),
+                    }
+                }
+            }
+        )*
+    };
+}
+answer_from_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i128, isize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        if text.contains('\n') {
+            Answer::Text(text)
+        } else {
+            Answer::Str(text)
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::from(text.to_string())
+    }
+}
+
+// NOTE: JSON numbers are not safe beyond 64 bits (many tools read them as floats), so only an
+//       `Int` is stored as a number, others are stored as a string.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Int(value) => serializer.serialize_i64(*value),
+            answer => serializer.serialize_str(&answer.to_string()),
+        }
+    }
+}
+
+struct AnswerVisitor;
+
+impl Visitor<'_> for AnswerVisitor {
+    type Value = Answer;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an integer or a string")
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Answer, E> {
+        Ok(Answer::from(value))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Answer, E> {
+        Ok(Answer::from(value))
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<Answer, E> {
+        Ok(Answer::from_text(text))
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_and_eq() {
+        assert_eq!(Answer::from(42_usize), Answer::Int(42));
+        assert_eq!(Answer::from(-3_i32), Answer::Int(-3));
+        assert_eq!(Answer::from(u64::MAX), Answer::Big(u64::MAX.to_string()));
+        // The same number is always the same variant, whatever its type or text
+        assert_eq!(
+            Answer::from(u128::MAX),
+            Answer::from_text(&u128::MAX.to_string())
+        );
+        assert_eq!(
+            Answer::from_text("-000123456789012345678901234567890123456789"),
+            Answer::Big("-123456789012345678901234567890123456789".to_string())
+        );
+        assert_eq!(Answer::from_text("+0042"), Answer::Int(42));
+        assert!(matches!(Answer::from_text("4-2"), Answer::Str(_)));
+        assert_eq!(Answer::from("ABC"), Answer::Str("ABC".to_string()));
+        assert!(Answer::from("#..\n.#.").is_multiline());
+        // Equal when displayed the same way
+        assert_eq!(Answer::Int(42), Answer::Str("42".to_string()));
+        assert_ne!(Answer::Int(42), Answer::Int(43));
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let answers = vec![
+            Answer::Int(-42),
+            Answer::from(u128::MAX),
+            Answer::from("ABC"),
+            Answer::from("#.\n.#"),
+        ];
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(
+            json,
+            r##"[-42,"340282366920938463463374607431768211455","ABC","#.\n.#"]"##
+        );
+        let read_answers: Vec<Answer> = serde_json::from_str(&json).unwrap();
+        assert_eq!(read_answers, answers);
+        assert!(matches!(read_answers[1], Answer::Big(_)));
+        assert!(read_answers[3].is_multiline());
+    }
+}
@@ -0,0 +1,168 @@
+// Store of the known answers, for each day & input
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result as AnyResult};
+use serde::{Deserialize, Serialize};
+
+use crate::input::normalize;
+use crate::Answer;
+
+pub const DEFAULT_ANSWERS_PATH: &str = "./inputs/answers.json";
+
+/// Answers of both parts for a given input.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputAnswers {
+    /// Path (or label, e.g. `<stdin>`) of the input when the answers were recorded.
+    /// NOTE: Only informative, to know what input a hash is about when reading the file.
+    pub input: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part1: Option<Answer>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part2: Option<Answer>,
+    /// Answers the website said are wrong, never submitted again.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wrong: Vec<WrongAnswer>,
+    /// Time (in seconds since the Unix epoch) before which the website refuses answers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_until: Option<u64>,
+}
+
+/// An answer the website said is wrong, with its hint when there is one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WrongAnswer {
+    pub part: u8,
+    pub answer: Answer,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<Hint>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+impl InputAnswers {
+    pub fn part_mut(&mut self, part: u8) -> &mut Option<Answer> {
+        match part {
+            1 => &mut self.part1,
+            2 => &mut self.part2,
+            _ => unreachable!("There are only 2 parts in a day"),
+        }
+    }
+}
+
+/// The answers are keyed by day name, then by the hash of the input's content.
+///
+/// NOTE: Keying by content (and not by path) means the answers stay valid when the input is moved
+///       around, and that a custom input never gets the answers of the default one.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AnswersStore {
+    days: BTreeMap<String, BTreeMap<String, InputAnswers>>,
+}
+
+impl AnswersStore {
+    /// Loads the store from the given file, a missing file gives an empty store.
+    pub fn load(path: &Path) -> AnyResult<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read answers file {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid answers file {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> AnyResult<()> {
+        let mut content = serde_json::to_string_pretty(self)?;
+        content.push('\n');
+        std::fs::write(path, content)
+            .with_context(|| format!("Cannot write answers file {}", path.display()))
+    }
+
+    pub fn expected(&self, day_name: &str, input: &str, part: u8) -> Option<Answer> {
+        let answers = self.days.get(day_name)?.get(&input_hash(input))?;
+        match part {
+            1 => answers.part1.clone(),
+            2 => answers.part2.clone(),
+            _ => None,
+        }
+    }
+
+    pub fn record(
+        &mut self,
+        day_name: &str,
+        input_label: &str,
+        input: &str,
+        part: u8,
+        answer: Answer,
+    ) {
+        *self
+            .input_answers_mut(day_name, input_label, input)
+            .part_mut(part) = Some(answer);
+    }
+
+    /// Gives the answers of the input, created if there are none yet.
+    pub fn input_answers_mut(
+        &mut self,
+        day_name: &str,
+        input_label: &str,
+        input: &str,
+    ) -> &mut InputAnswers {
+        let answers = self
+            .days
+            .entry(day_name.to_string())
+            .or_default()
+            .entry(input_hash(input))
+            .or_default();
+        answers.input = input_label.to_string();
+        answers
+    }
+}
+
+/// Hashes the input's content to a short hex string.
+///
+/// NOTE: The normalized input is hashed, the same input with other line endings has the same
+///       answers.
+pub fn input_hash(input: &str) -> String {
+    format!("{:016x}", fnv1a(normalize(input, false).text.as_bytes()))
+}
+
+/// FNV-1a hash (64 bits).
+///
+/// NOTE: std's `DefaultHasher` is not guaranteed to give the same hash between Rust releases, but
+///       the hashes are stored in a file, so they must be stable.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_hash() {
+        // Reference values of FNV-1a 64
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(input_hash("1,2\r\n"), input_hash("1,2"));
+    }
+
+    #[test]
+    fn test_record_and_expected() {
+        let mut store = AnswersStore::default();
+        store.record("day42", "in.txt", "1,2,3", 2, Answer::Int(1234));
+        assert_eq!(store.expected("day42", "1,2,3", 2), Some(Answer::Int(1234)));
+        assert_eq!(store.expected("day42", "1,2,3", 1), None);
+        assert_eq!(store.expected("day42", "4,5,6", 2), None);
+        assert_eq!(store.expected("day01", "1,2,3", 2), None);
+    }
+}
@@ -0,0 +1,151 @@
+// Benchmark of the days' parts
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::outcome::PartOutcome;
+use crate::panics::catch_panic;
+use crate::{Answer, Day};
+
+/// Statistics over the durations of multiple runs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Computes the stats of the given samples, returns None if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let nb_samples = sorted.len();
+        let median = if nb_samples.is_multiple_of(2) {
+            (sorted[nb_samples / 2 - 1] + sorted[nb_samples / 2]) / 2
+        } else {
+            sorted[nb_samples / 2]
+        };
+
+        let secs: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / nb_samples as f64;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / nb_samples as f64;
+
+        Some(Self {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // NOTE: Debug repr of a Duration picks a nice unit (ns, µs, ms, s) and supports precision.
+        write!(
+            f,
+            "{:>10.2?} {:>10.2?} {:>10.2?} {:>10.2?}",
+            self.min, self.median, self.mean, self.stddev
+        )
+    }
+}
+
+/// How to run the benchmark.
+#[derive(Debug, Clone, Copy)]
+pub struct BenchConfig {
+    /// Number of measured runs.
+    pub runs: usize,
+    /// Number of runs before the measured ones, to warm-up caches & co.
+    pub warmup: usize,
+}
+
+fn measure_runs<T>(config: &BenchConfig, mut func: impl FnMut() -> T) -> Vec<Duration> {
+    for _ in 0..config.warmup {
+        func();
+    }
+    (0..config.runs)
+        .map(|_| {
+            let start = Instant::now();
+            func();
+            start.elapsed()
+        })
+        .collect()
+}
+
+/// Benches the parsing and both parts of the day, then displays the stats.
+///
+/// The parts are solved with an input parsed once, so their stats do not include the parsing.
+pub fn bench_day(day: &dyn Day, input: &str, expected: [Option<Answer>; 2], config: &BenchConfig) {
+    println!(
+        "=>> {} <<= ({} runs, {} warm-up runs)",
+        day.name(),
+        config.runs,
+        config.warmup
+    );
+    println!(
+        "{:8}{:>10} {:>10} {:>10} {:>10}",
+        "", "min", "median", "mean", "stddev"
+    );
+
+    let parsed = match catch_panic(|| day.parse(input)) {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(err)) => {
+            println!("💥 Cannot parse the input:\n{}", err);
+            return;
+        }
+        Err(report) => {
+            println!("💥 Panicked while parsing the input: {}", report);
+            return;
+        }
+    };
+    let parse_samples = measure_runs(config, || day.parse(input));
+    if let Some(stats) = Stats::from_samples(&parse_samples) {
+        println!("{:8}{}", "Parse", stats);
+    }
+
+    for (part, expected) in (1..=2).zip(expected) {
+        // Check the part works before measuring it, no need to bench a failing part.
+        let outcome = PartOutcome::solve(|| parsed.solve(part), expected);
+        if !outcome.status.is_ok() {
+            outcome.report(&format!("Part{}", part));
+            continue;
+        }
+
+        let part_samples = measure_runs(config, || parsed.solve(part));
+        if let Some(stats) = Stats::from_samples(&part_samples) {
+            println!(
+                "{:8}{}   {}",
+                format!("Part{}", part),
+                stats,
+                outcome.status.symbol()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples: Vec<_> = [4, 1, 3, 2]
+            .into_iter()
+            .map(Duration::from_millis)
+            .collect();
+        let stats = Stats::from_samples(&samples).unwrap();
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        // variance = (1.5² + 0.5² + 0.5² + 1.5²) / 4 = 1.25 => stddev ≈ 1.118ms
+        assert_eq!(stats.stddev.as_micros(), 1118);
+
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+}
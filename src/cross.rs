@@ -0,0 +1,148 @@
+// Cross-checking the parts with slow but simple reference implementations, on generated inputs
+
+use std::time::Duration;
+
+use crate::outcome::{error_status, parse_failed, PartOutcome, PartStatus};
+use crate::panics::catch_panic;
+use crate::solution::{solve_part, Reference};
+use crate::Solution;
+
+/// Sizes of the generated inputs, increasing so the first input giving a different answer is
+/// as small as possible.
+pub const SIZES: [usize; 8] = [1, 2, 3, 5, 8, 13, 21, 34];
+
+/// Seed of the generated inputs, always the same so a difference can be found again.
+pub const SEED: u64 = 2021;
+
+/// Small pseudo-random generator (SplitMix64), enough to generate inputs.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Random number in the range (the range must not be empty).
+    pub fn range(&mut self, range: std::ops::Range<u64>) -> u64 {
+        range.start + self.next_u64() % (range.end - range.start)
+    }
+}
+
+/// Solves the parts that have a reference implementation, with the answer of their reference as
+/// the expected answer.
+pub fn cross_check<S: Solution>(raw_input: &str) -> Vec<(u8, PartOutcome)> {
+    let references = S::references();
+    let status = match catch_panic(|| S::parse(raw_input)) {
+        Ok(Ok(input)) => {
+            return (references.into_iter())
+                .map(|(part, reference)| (part, check_part::<S>(part, reference, &input)))
+                .collect();
+        }
+        Ok(Err(err)) => error_status(err),
+        Err(report) => PartStatus::Panicked(report.to_string()),
+    };
+    let parts: Vec<_> = references.iter().map(|(part, _)| *part).collect();
+    let outcomes = parse_failed(status, Duration::ZERO, &parts);
+    parts.into_iter().zip(outcomes).collect()
+}
+
+fn check_part<S: Solution>(
+    part: u8,
+    reference: Reference<S::Input>,
+    input: &S::Input,
+) -> PartOutcome {
+    match PartOutcome::solve(|| reference(input), None).status {
+        PartStatus::Unknown(answer) => {
+            PartOutcome::solve(|| solve_part::<S>(part, input), Some(answer))
+        }
+        PartStatus::Failed(msg) | PartStatus::Panicked(msg) => {
+            PartOutcome::failed(format!("The reference failed: {}", msg))
+        }
+        status => PartOutcome::failed(format!("The reference failed: {}", status.name())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Answer, PartResult, DAYS};
+
+    struct TestDay;
+
+    impl Solution for TestDay {
+        type Input = Vec<i64>;
+        const NAME: &'static str = "test";
+        const TITLE: &'static str = "Test";
+
+        fn parse(raw_input: &str) -> anyhow::Result<Vec<i64>> {
+            Ok(raw_input
+                .trim()
+                .split(',')
+                .map(str::parse)
+                .collect::<Result<_, _>>()?)
+        }
+
+        // Sum of the numbers, but wrong with more than 3 numbers
+        fn part1(input: &Vec<i64>) -> PartResult {
+            Ok(input.iter().take(3).sum::<i64>().into())
+        }
+
+        fn part2(_: &Vec<i64>) -> PartResult {
+            Ok(0.into())
+        }
+
+        fn references() -> Vec<(u8, Reference<Vec<i64>>)> {
+            vec![(1, |input| Ok(input.iter().sum::<i64>().into()))]
+        }
+    }
+
+    #[test]
+    fn test_cross_check() {
+        let outcomes = cross_check::<TestDay>("1,2,3");
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].1.status, PartStatus::Solved(Answer::Int(6)));
+
+        let outcomes = cross_check::<TestDay>("1,2,3,4");
+        let expected = PartStatus::Mismatch {
+            expected: Answer::Int(10),
+            got: Answer::Int(6),
+        };
+        assert_eq!(outcomes[0].1.status, expected);
+
+        let outcomes = cross_check::<TestDay>("1,x");
+        assert_eq!(outcomes[0].1.status.name(), "failed");
+    }
+
+    #[test]
+    fn test_all_cross_checks() {
+        for day in DAYS {
+            let mut rng = Rng::new(SEED);
+            for size in SIZES {
+                let Some(input) = day.generate_input(size, &mut rng) else {
+                    break;
+                };
+                for (part, outcome) in day.cross_check(&input) {
+                    let name = day.name();
+                    assert!(
+                        outcome.status.is_solved(),
+                        "{} part{} on {:?}",
+                        name,
+                        part,
+                        input
+                    );
+                }
+            }
+        }
+    }
+}
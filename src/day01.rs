@@ -1,22 +1,17 @@
 // day 01
 
-use chumsky as c;
+use anyhow::Result as AnyResult;
 use chumsky::prelude::*;
 
+use crate::solution::{Example, Solution};
+use crate::{diagnostic, parsing, PartResult};
+
 // Format:
 //   123
 //   4567
 //   ...
-fn input_parser() -> impl Parser<char, Vec<usize>, Error = Simple<char>> {
-    c::text::int(10)
-        // Q: Why do I need to specify `: String` for map?
-        // Answer from author: (ref: https://github.com/zesterer/chumsky/discussions/40#discussioncomment-1750744)
-        //   Many of the parsers in the text module are generic across Unicode (via char) and
-        //   ASCII (via u8) characters, including ident. The String is required to tell text::int
-        //   that it should be parsing chars and not u8s. You could also swap out String with Vec<u8>
-        //   if you wanted a parser that parses integers using ASCII bytes.
-        .map(|s: String| s.parse().unwrap())
-        .separated_by(c::text::newline())
+pub fn input_parser() -> impl Parser<char, Vec<usize>, Error = Simple<char>> {
+    parsing::lines(parsing::unsigned())
 }
 
 #[derive(Debug)]
@@ -45,26 +40,71 @@ fn count_increasing_measures(measures: &[usize]) -> usize {
         .count()
 }
 
-pub fn solve_part1(raw_input: &str) -> (usize, Option<usize>) {
-    let measures = input_parser().parse(raw_input).unwrap();
-    // KEEP: ...parse().map_err(|errs| anyhow::anyhow!("parsing errors: {:?}", errs))?;
+static EXAMPLE_INPUT: &str = r#"
+199
+200
+208
+210
+200
+207
+240
+269
+260
+263
+"#;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<usize>;
+    const NAME: &'static str = "day01";
+    const TITLE: &'static str = "Sonar Sweep";
+
+    fn parse(raw_input: &str) -> AnyResult<Self::Input> {
+        diagnostic::parse(input_parser(), raw_input)
+    }
+
+    fn part1(measures: &Self::Input) -> PartResult {
+        let result = count_increasing_measures(measures);
+        // dbg!(result);
+        Ok(result.into())
+    }
 
-    let result = count_increasing_measures(&measures);
-    // dbg!(result);
-    (result, Some(1502))
+    fn part2(measures: &Self::Input) -> PartResult {
+        // Smoothed measures by sum-ing measurements 3 by 3 and using that sum.
+        let smoothed_measures: Vec<_> = measures
+            .iter()
+            .zip(measures.iter().skip(1))
+            .zip(measures.iter().skip(2))
+            .map(|((item0, item1), item2)| item0 + item1 + item2)
+            .collect();
+
+        let result = count_increasing_measures(&smoothed_measures);
+        // dbg!(result);
+        Ok(result.into())
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT, 7, 5)]
+    }
 }
 
-pub fn solve_part2(raw_input: &str) -> (usize, Option<usize>) {
-    let measures = input_parser().parse(raw_input).unwrap();
-    // Smoothed measures by sum-ing measurements 3 by 3 and using that sum.
-    let smoothed_measures: Vec<_> = measures
-        .iter()
-        .zip(measures.iter().skip(1))
-        .zip(measures.iter().skip(2))
-        .map(|((item0, item1), item2)| item0 + item1 + item2)
-        .collect();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
 
-    let result = count_increasing_measures(&smoothed_measures);
-    // dbg!(result);
-    (result, Some(1538))
+    proptest! {
+        #[test]
+        fn prop_parse_and_solve(
+            depths in prop::collection::vec(0..10_000_usize, 1..300),
+            line_ending in prop_oneof![Just("\n"), Just("\r\n")],
+        ) {
+            let lines: Vec<_> = depths.iter().map(|depth| depth.to_string()).collect();
+            let input = Day01::parse(&lines.join(line_ending)).unwrap();
+            prop_assert_eq!(&input, &depths);
+            Day01::part1(&input).unwrap();
+            Day01::part2(&input).unwrap();
+        }
+    }
 }
@@ -1,10 +1,14 @@
 // day 02
 
+use anyhow::Result as AnyResult;
 use chumsky as c;
 use chumsky::prelude::*;
 
-#[derive(Debug)]
-enum Cmd {
+use crate::solution::{Example, Solution};
+use crate::{diagnostic, parsing, PartResult};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Cmd {
     Forward(usize),
     Up(usize),
     Down(usize),
@@ -18,49 +22,118 @@ enum Cmd {
 //   down 8
 //   forward 2
 //   ...
-fn input_parser() -> impl Parser<char, Vec<Cmd>, Error = Simple<char>> {
-    let number = c::text::int(10).map(|s: String| s.parse().unwrap());
-    let cmd_line = c::text::ident()
+pub fn input_parser() -> impl Parser<char, Vec<Cmd>, Error = Simple<char>> {
+    let number = parsing::unsigned();
+    let cmd_name = c::text::ident().try_map(|cmd: String, span| match cmd.as_str() {
+        "forward" | "up" | "down" => Ok(cmd),
+        _ => Err(Simple::custom(span, format!("unknown command '{}'", cmd))),
+    });
+    let cmd_line = cmd_name
         .then_ignore(just(' '))
         .then(number)
         .map(|(cmd, by_count)| match cmd.as_str() {
             "forward" => Cmd::Forward(by_count),
             "up" => Cmd::Up(by_count),
             "down" => Cmd::Down(by_count),
-            _ => unreachable!(),
+            _ => unreachable!(), // Other commands are rejected by `cmd_name`
         });
-    cmd_line.separated_by(c::text::newline())
+    parsing::lines(cmd_line)
 }
 
-pub fn solve_part1(raw_input: &str) -> (usize, Option<usize>) {
-    let cmds = input_parser().parse(raw_input).unwrap();
+static EXAMPLE_INPUT: &str = r#"
+forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2
+"#;
 
-    let (mut hpos, mut depth) = (0, 0);
-    for cmd in cmds {
-        match cmd {
-            Cmd::Forward(by_count) => hpos += by_count,
-            Cmd::Down(by_count) => depth += by_count,
-            Cmd::Up(by_count) => depth -= by_count,
-        }
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Cmd>;
+    const NAME: &'static str = "day02";
+    const TITLE: &'static str = "Dive!";
+
+    fn parse(raw_input: &str) -> AnyResult<Self::Input> {
+        diagnostic::parse(input_parser(), raw_input)
     }
 
-    (hpos * depth, Some(1813801))
-}
+    fn part1(cmds: &Self::Input) -> PartResult {
+        let (mut hpos, mut depth) = (0, 0);
+        for cmd in cmds {
+            match cmd {
+                Cmd::Forward(by_count) => hpos += by_count,
+                Cmd::Down(by_count) => depth += by_count,
+                Cmd::Up(by_count) => depth -= by_count,
+            }
+        }
 
-pub fn solve_part2(raw_input: &str) -> (usize, Option<usize>) {
-    let cmds = input_parser().parse(raw_input).unwrap();
+        Ok((hpos * depth).into())
+    }
 
-    let (mut hpos, mut depth, mut aim) = (0, 0, 0);
-    for cmd in cmds {
-        match cmd {
-            Cmd::Forward(by_count) => {
-                hpos += by_count;
-                depth += by_count * aim;
+    fn part2(cmds: &Self::Input) -> PartResult {
+        let (mut hpos, mut depth, mut aim) = (0, 0, 0);
+        for cmd in cmds {
+            match cmd {
+                Cmd::Forward(by_count) => {
+                    hpos += by_count;
+                    depth += by_count * aim;
+                }
+                Cmd::Down(by_count) => aim += by_count,
+                Cmd::Up(by_count) => aim -= by_count,
             }
-            Cmd::Down(by_count) => aim += by_count,
-            Cmd::Up(by_count) => aim -= by_count,
         }
+
+        Ok((hpos * depth).into())
     }
 
-    (hpos * depth, Some(1960569556))
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT, 150, 900)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Random commands, that never go above the surface.
+    fn cmds_strategy() -> impl Strategy<Value = Vec<Cmd>> {
+        prop::collection::vec((0..3, 1..10_usize), 1..200).prop_map(|raw_cmds| {
+            let mut aim = 0;
+            (raw_cmds.into_iter())
+                .map(|(kind, by_count)| match kind {
+                    0 => Cmd::Forward(by_count),
+                    1 => {
+                        aim += by_count;
+                        Cmd::Down(by_count)
+                    }
+                    _ => {
+                        let by_count = by_count.min(aim);
+                        aim -= by_count;
+                        Cmd::Up(by_count)
+                    }
+                })
+                .collect()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parse_and_solve(cmds in cmds_strategy()) {
+            let lines: Vec<_> = (cmds.iter())
+                .map(|cmd| match cmd {
+                    Cmd::Forward(by_count) => format!("forward {}", by_count),
+                    Cmd::Up(by_count) => format!("up {}", by_count),
+                    Cmd::Down(by_count) => format!("down {}", by_count),
+                })
+                .collect();
+            let input = Day02::parse(&lines.join("\n")).unwrap();
+            prop_assert_eq!(&input, &cmds);
+            Day02::part1(&input).unwrap();
+            Day02::part2(&input).unwrap();
+        }
+    }
 }
@@ -1,8 +1,11 @@
 // day 03
 
-use chumsky as c;
+use anyhow::Result as AnyResult;
 use chumsky::prelude::*;
 
+use crate::solution::{Example, Solution};
+use crate::{diagnostic, parsing, PartResult};
+
 // Format:
 //   00100...
 //   11110...
@@ -17,13 +20,14 @@ use chumsky::prelude::*;
 //   00010...
 //   01010...
 //   ...
-fn input_parser() -> impl Parser<char, (u8, Vec<usize>), Error = Simple<char>> {
+pub fn input_parser() -> impl Parser<char, (u8, Vec<usize>), Error = Simple<char>> {
     let bits = one_of("01").repeated().at_least(1);
-    let bit_based_number = bits.collect::<String>().map(|s: String| {
-        let num_from_bits = usize::from_str_radix(&s, 2).unwrap();
-        (s.len() as u8, num_from_bits)
+    let bit_based_number = bits.collect::<String>().try_map(|s: String, span| {
+        let num_from_bits = usize::from_str_radix(&s, 2)
+            .map_err(|err| Simple::custom(span, format!("invalid number {}: {}", s, err)))?;
+        Ok((s.len() as u8, num_from_bits))
     });
-    let report_parser = bit_based_number.separated_by(c::text::newline());
+    let report_parser = parsing::lines(bit_based_number);
     // extract the numbers and the bit length of the numbers, to be directly usable :)
     report_parser.map(|diag_report: Vec<(u8, usize)>| {
         let max_len = diag_report
@@ -79,88 +83,133 @@ fn bit_popularity_at_idx(numbers: &[usize], bit_idx: u8) -> BitPopularity {
     }
     match is1_diff {
         n if n > 0 => BitPopularity::One,
-        n if n == 0 => BitPopularity::Equal,
+        0 => BitPopularity::Equal,
         n if n < 0 => BitPopularity::Zero,
         _ => unreachable!(),
     }
 }
 
-pub fn solve_part1(raw_input: &str) -> (usize, Option<usize>) {
-    let (max_len, numbers) = input_parser().parse(raw_input).unwrap();
+static EXAMPLE_INPUT: &str = r#"
+00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010
+"#;
 
-    let mut gamma_rate = 0 as usize;
-    for bit_idx in (0u8..max_len).rev() {
-        if let BitPopularity::One | BitPopularity::Equal = bit_popularity_at_idx(&numbers, bit_idx)
-        {
-            // println!("most common is 1 for bit idx: {}", bit_idx);
-            gamma_rate += 1 << bit_idx;
-        }
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = (u8, Vec<usize>);
+    const NAME: &'static str = "day03";
+    const TITLE: &'static str = "Binary Diagnostic";
+
+    fn parse(raw_input: &str) -> AnyResult<Self::Input> {
+        diagnostic::parse(input_parser(), raw_input)
     }
-    // dbg!(gamma_rate);
 
-    // Q: Is there a better way to get a number with all-1 bits of size `max_len` ?
-    // (e.g for size 5: 0b11111)
-    let max_len_mask_of_1 = (0..max_len).fold(0, |acc, idx| acc | (1 << idx));
-    // println!("mask of 1 (size {}): {:#012b}", max_len, max_len_mask_of_1);
+    fn part1(input: &Self::Input) -> PartResult {
+        let (max_len, numbers) = (input.0, &input.1);
+
+        let mut gamma_rate = 0_usize;
+        for bit_idx in (0u8..max_len).rev() {
+            if let BitPopularity::One | BitPopularity::Equal =
+                bit_popularity_at_idx(numbers, bit_idx)
+            {
+                // println!("most common is 1 for bit idx: {}", bit_idx);
+                gamma_rate += 1 << bit_idx;
+            }
+        }
+        // dbg!(gamma_rate);
+
+        // Q: Is there a better way to get a number with all-1 bits of size `max_len` ?
+        // (e.g for size 5: 0b11111)
+        let max_len_mask_of_1 = (0..max_len).fold(0, |acc, idx| acc | (1 << idx));
+        // println!("mask of 1 (size {}): {:#012b}", max_len, max_len_mask_of_1);
 
-    let epsilon_rate = max_len_mask_of_1 ^ gamma_rate; // invert all `max_len` bits
-                                                       // dbg!((gamma_rate, epsilon_rate));
+        let epsilon_rate = max_len_mask_of_1 ^ gamma_rate; // invert all `max_len` bits
+                                                           // dbg!((gamma_rate, epsilon_rate));
 
-    let power_consumption = gamma_rate * epsilon_rate;
-    (power_consumption, Some(841526))
-}
+        let power_consumption = gamma_rate * epsilon_rate;
+        Ok(power_consumption.into())
+    }
 
-pub fn solve_part2(raw_input: &str) -> (usize, Option<usize>) {
-    let (max_len, numbers) = input_parser().parse(raw_input).unwrap();
+    fn part2(input: &Self::Input) -> PartResult {
+        let (max_len, numbers) = (input.0, &input.1);
 
-    let oxygen_generator_rating = {
-        let mut numbers = numbers.clone();
-        for bit_idx in (0u8..max_len).rev() {
-            let bit_popularity = bit_popularity_at_idx(&numbers, bit_idx);
-            let bit_criteria = bit_popularity.value_or(1); // criteria when Equal: 1
-            numbers.retain(|num| number_get_bit_at_idx(*num, bit_idx) == bit_criteria.into());
+        let oxygen_generator_rating = {
+            let mut numbers = numbers.clone();
+            for bit_idx in (0u8..max_len).rev() {
+                let bit_popularity = bit_popularity_at_idx(&numbers, bit_idx);
+                let bit_criteria = bit_popularity.value_or(1); // criteria when Equal: 1
+                numbers.retain(|num| {
+                    number_get_bit_at_idx(*num, bit_idx) == usize::from(bit_criteria)
+                });
 
-            // let numbers_as_bits: Vec<String> = numbers.iter().map(|num| format!("{:#012b}", num)).collect();
-            // dbg!(bit_idx, bit_popularity, bit_criteria, &numbers_as_bits);
+                // let numbers_as_bits: Vec<String> = numbers.iter().map(|num| format!("{:#012b}", num)).collect();
+                // dbg!(bit_idx, bit_popularity, bit_criteria, &numbers_as_bits);
 
-            if numbers.len() == 1 {
-                break;
+                if numbers.len() == 1 {
+                    break;
+                }
             }
-        }
-        // There should be only one number left, take it out!
-        numbers.pop().unwrap()
-    };
-    // dbg!(oxygen_generator_rating);
-    // println!("-------");
-
-    let co2_scrubber_rating = {
-        let mut numbers = numbers.clone();
-        for bit_idx in (0u8..max_len).rev() {
-            let bit_popularity = bit_popularity_at_idx(&numbers, bit_idx);
-            let bit_least_popularity = bit_popularity.invert_popularity();
-            // In this case we look for the least popularity:
-            let bit_criteria = bit_least_popularity.value_or(0); // criteria when Equal: 0
-            numbers.retain(|num| number_get_bit_at_idx(*num, bit_idx) == bit_criteria.into());
+            // There should be only one number left, take it out!
+            numbers.pop().unwrap()
+        };
+        // dbg!(oxygen_generator_rating);
+        // println!("-------");
+
+        let co2_scrubber_rating = {
+            let mut numbers = numbers.clone();
+            for bit_idx in (0u8..max_len).rev() {
+                let bit_popularity = bit_popularity_at_idx(&numbers, bit_idx);
+                let bit_least_popularity = bit_popularity.invert_popularity();
+                // In this case we look for the least popularity:
+                let bit_criteria = bit_least_popularity.value_or(0); // criteria when Equal: 0
+                // NOTE: When all numbers have the same bit, the least common bit is in none of
+                //       them, and they must all be kept (not all removed).
+                if numbers
+                    .iter()
+                    .any(|num| number_get_bit_at_idx(*num, bit_idx) == usize::from(bit_criteria))
+                {
+                    numbers.retain(|num| {
+                        number_get_bit_at_idx(*num, bit_idx) == usize::from(bit_criteria)
+                    });
+                }
 
-            // let numbers_as_bits: Vec<String> = numbers.iter().map(|num| format!("{:#012b}", num)).collect();
-            // dbg!(bit_idx, bit_popularity, bit_criteria, &numbers_as_bits);
+                // let numbers_as_bits: Vec<String> = numbers.iter().map(|num| format!("{:#012b}", num)).collect();
+                // dbg!(bit_idx, bit_popularity, bit_criteria, &numbers_as_bits);
 
-            if numbers.len() == 1 {
-                break;
+                if numbers.len() == 1 {
+                    break;
+                }
             }
-        }
-        // There should be only one number left, take it out!
-        numbers.pop().unwrap()
-    };
-    // dbg!(co2_scrubber_rating);
+            // There should be only one number left, take it out!
+            numbers.pop().unwrap()
+        };
+        // dbg!(co2_scrubber_rating);
 
-    let life_support_rating = oxygen_generator_rating * co2_scrubber_rating;
-    (life_support_rating, Some(4790390))
+        let life_support_rating = oxygen_generator_rating * co2_scrubber_rating;
+        Ok(life_support_rating.into())
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT, 198, 230)]
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Answer;
+    use proptest::prelude::*;
 
     #[test]
     fn test_parser() {
@@ -183,31 +232,46 @@ mod tests {
         assert!(number_get_bit_at_idx(NUM, 7) == 0);
     }
 
-    // NOTE: didn't find a simple way to unindent a set of indented lines.. for the
-    // original input_parser to work.
-    static EXAMPLE_INPUT: &str = r#"00100
-11110
-10110
-10111
-10101
-01111
-00111
-11100
-10000
-11001
-00010
-01010
-"#;
-
     #[test]
     fn example_part1() {
-        let (result, _) = solve_part1(EXAMPLE_INPUT);
-        assert!(result == 198);
+        let input = Day03::parse(EXAMPLE_INPUT).unwrap();
+        let result = Day03::part1(&input).unwrap();
+        assert_eq!(result, Answer::Int(198));
     }
 
     #[test]
     fn example_part2() {
-        let (result, _) = solve_part2(EXAMPLE_INPUT);
-        assert!(result == 230);
+        let input = Day03::parse(EXAMPLE_INPUT).unwrap();
+        let result = Day03::part2(&input).unwrap();
+        assert_eq!(result, Answer::Int(230));
+    }
+
+    #[test]
+    fn test_part2_same_first_bit() {
+        // The least common first bit is `0`, in none of the numbers: they are all kept for the
+        // co2 scrubber rating (instead of all removed).
+        let input = Day03::parse("110\n111").unwrap();
+        let result = Day03::part2(&input).unwrap();
+        assert_eq!(result, Answer::Int(7 * 6));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parse_and_solve(
+            reports in (1..=12_usize).prop_flat_map(|len| {
+                prop::collection::vec(prop::collection::vec(prop::bool::ANY, len), 1..100)
+            })
+        ) {
+            let lines: Vec<String> = (reports.iter())
+                .map(|bits| bits.iter().map(|bit| if *bit { '1' } else { '0' }).collect())
+                .collect();
+            let numbers: Vec<_> = (lines.iter())
+                .map(|line| usize::from_str_radix(line, 2).unwrap())
+                .collect();
+            let input = Day03::parse(&lines.join("\n")).unwrap();
+            prop_assert_eq!(&input, &(lines[0].len() as u8, numbers));
+            Day03::part1(&input).unwrap();
+            Day03::part2(&input).unwrap();
+        }
     }
 }
@@ -1,9 +1,13 @@
 // day 04
 
+use anyhow::Result as AnyResult;
 use chumsky as c;
 use chumsky::prelude::*;
 
-type BingoNum = u8;
+use crate::solution::{Example, Solution};
+use crate::{diagnostic, parsing, PartResult};
+
+pub type BingoNum = u8;
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 struct BingoCell {
@@ -17,7 +21,7 @@ impl BingoCell {
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
-struct BingoBoard {
+pub struct BingoBoard {
     lines: Vec<Vec<BingoCell>>,
 }
 
@@ -36,12 +40,12 @@ impl BingoBoard {
             .collect();
         BingoBoard { lines }
     }
-    fn is_winning(&self) -> bool {
+    pub fn is_winning(&self) -> bool {
         let has_win_line = (0..5).any(|line| (0..5).all(|col| self.is_marked_at(line, col)));
         let has_win_col = (0..5).any(|col| (0..5).all(|line| self.is_marked_at(line, col)));
         has_win_line || has_win_col
     }
-    fn is_marked_at(&self, line: u8, col: u8) -> bool {
+    pub fn is_marked_at(&self, line: u8, col: u8) -> bool {
         // println!("Checking cell is marked at (L{}, C{})", line, col);
         unsafe {
             self.lines
@@ -50,7 +54,7 @@ impl BingoBoard {
                 .marked
         }
     }
-    fn mark_with(&mut self, number: BingoNum) {
+    pub fn mark_with(&mut self, number: BingoNum) {
         for line in self.lines.iter_mut() {
             for cell in line.iter_mut() {
                 if cell.num == number {
@@ -59,12 +63,12 @@ impl BingoBoard {
             }
         }
     }
-    fn unmarked_nums(&self) -> Vec<BingoNum> {
+    pub fn unmarked_nums(&self) -> Vec<BingoNum> {
         self.lines
             .iter()
             .flat_map(|line| {
                 line.iter()
-                    .filter_map(|cell| (!cell.marked).then(|| cell.num))
+                    .filter_map(|cell| (!cell.marked).then_some(cell.num))
                     .collect::<Vec<_>>()
             })
             .collect()
@@ -81,7 +85,7 @@ impl std::fmt::Display for BingoBoard {
                     write!(f, "{:2} ", cell.num)?;
                 }
             }
-            write!(f, "\n")?;
+            writeln!(f)?;
         }
         Ok(())
     }
@@ -99,12 +103,11 @@ impl std::fmt::Display for BingoBoard {
 //    3 27  1 93 42      -- board 2
 //   21  0  3 18  5
 //   ...
-fn input_parser() -> impl Parser<char, (Vec<BingoNum>, Vec<BingoBoard>), Error = Simple<char>> {
-    let number = c::text::int(10).map(|s: String| s.parse().unwrap());
+pub fn input_parser() -> impl Parser<char, (Vec<BingoNum>, Vec<BingoBoard>), Error = Simple<char>> {
+    let number = parsing::unsigned();
     let newline = c::text::newline();
-    let double_newline = newline.then(newline);
 
-    let random_numbers = number.separated_by(just(','));
+    let random_numbers = parsing::comma_list(number);
 
     let board_line = just(' ')
         .ignore_then(number)
@@ -118,12 +121,11 @@ fn input_parser() -> impl Parser<char, (Vec<BingoNum>, Vec<BingoBoard>), Error =
         .exactly(5)
         .map(BingoBoard::from_input);
 
-    let boards = board.separated_by(double_newline);
+    let boards = parsing::blocks(board);
 
     random_numbers
-        .then_ignore(double_newline)
+        .then_ignore(parsing::blank_line())
         .then(boards)
-        .then_ignore(newline.then(end()))
 }
 
 // Returns an iterator over the results of winning boards.
@@ -164,29 +166,70 @@ fn calc_final_score(unmarked_nums: &[BingoNum], last_rand_num: BingoNum) -> usiz
     sum_unmarked * (last_rand_num as usize)
 }
 
-pub fn solve_part1(raw_input: &str) -> (usize, Option<usize>) {
-    let (random_numbers, boards) = input_parser().parse(raw_input).expect("parsing error");
+static EXAMPLE_INPUT: &str = r#"
+7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
 
-    let mut board_win_results = get_boards_win_results(random_numbers, boards);
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
 
-    let (last_rand_num, win_board) = board_win_results.next().unwrap();
-    let score = calc_final_score(&win_board.unmarked_nums(), last_rand_num);
-    (score, Some(38594))
-}
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7
+"#;
+
+/// Bingo simulation.
+pub struct Day04;
 
-pub fn solve_part2(raw_input: &str) -> (usize, Option<usize>) {
-    let (random_numbers, boards) = input_parser().parse(raw_input).expect("parsing error");
+impl Solution for Day04 {
+    type Input = (Vec<BingoNum>, Vec<BingoBoard>);
+    const NAME: &'static str = "day04";
+    const TITLE: &'static str = "Giant Squid";
+
+    fn parse(raw_input: &str) -> AnyResult<Self::Input> {
+        diagnostic::parse(input_parser(), raw_input)
+    }
 
-    let board_win_results = get_boards_win_results(random_numbers, boards);
+    fn part1(input: &Self::Input) -> PartResult {
+        let (random_numbers, boards) = input.clone();
+
+        let mut board_win_results = get_boards_win_results(random_numbers, boards);
+
+        let (last_rand_num, win_board) = board_win_results.next().unwrap();
+        let score = calc_final_score(&win_board.unmarked_nums(), last_rand_num);
+        Ok(score.into())
+    }
+
+    fn part2(input: &Self::Input) -> PartResult {
+        let (random_numbers, boards) = input.clone();
+
+        let board_win_results = get_boards_win_results(random_numbers, boards);
+
+        let (last_rand_num, win_board) = board_win_results.last().unwrap();
+        let score = calc_final_score(&win_board.unmarked_nums(), last_rand_num);
+        Ok(score.into())
+    }
 
-    let (last_rand_num, win_board) = board_win_results.last().unwrap();
-    let score = calc_final_score(&win_board.unmarked_nums(), last_rand_num);
-    (score, Some(21184))
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT, 4512, 1924)]
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Answer;
+    use proptest::prelude::*;
 
     #[test]
     fn test_basic_parsing() {
@@ -219,28 +262,6 @@ mod tests {
         assert_eq!(boards, vec![expected_board]);
     }
 
-    static EXAMPLE_INPUT: &str = r#"
-7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
-
-22 13 17 11  0
- 8  2 23  4 24
-21  9 14 16  7
- 6 10  3 18  5
- 1 12 20 15 19
-
- 3 15  0  2 22
- 9 18 13 17  5
-19  8  7 25 23
-20 11 10 24  4
-14 21 16 12  6
-
-14 21 17 24  4
-10 16 15  9 19
-18  8 23 26 20
-22 11 13  6  5
- 2  0 12  3  7
-"#;
-
     #[test]
     fn test_parse_example_input() {
         let (rand_nums, boards) = input_parser()
@@ -252,13 +273,51 @@ mod tests {
 
     #[test]
     fn test_example_part1() {
-        let (result, _) = solve_part1(EXAMPLE_INPUT.trim());
-        assert_eq!(result, 4512);
+        let input = Day04::parse(EXAMPLE_INPUT).unwrap();
+        let result = Day04::part1(&input).unwrap();
+        assert_eq!(result, Answer::Int(4512));
     }
 
     #[test]
     fn test_example_part2() {
-        let (result, _) = solve_part2(EXAMPLE_INPUT.trim());
-        assert_eq!(result, 1924);
+        let input = Day04::parse(EXAMPLE_INPUT).unwrap();
+        let result = Day04::part2(&input).unwrap();
+        assert_eq!(result, Answer::Int(1924));
+    }
+
+    /// Random boards of distinct numbers, and random numbers with all the numbers of the boards
+    /// (so some boards always win).
+    fn bingo_strategy() -> impl Strategy<Value = (Vec<BingoNum>, Vec<Vec<BingoNum>>)> {
+        let all_nums: Vec<BingoNum> = (0..100).collect();
+        let board = prop::sample::subsequence(all_nums.clone(), 25).prop_shuffle();
+        (
+            Just(all_nums).prop_shuffle(),
+            prop::collection::vec(board, 1..10),
+        )
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+        #[test]
+        fn prop_parse_and_solve((random_numbers, boards_nums) in bingo_strategy()) {
+            let mut raw_input = (random_numbers.iter())
+                .map(|num| num.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            for board_nums in &boards_nums {
+                raw_input += "\n";
+                for line in board_nums.chunks(5) {
+                    let line: Vec<_> = line.iter().map(|num| format!("{:2}", num)).collect();
+                    raw_input += &format!("\n{}", line.join(" "));
+                }
+            }
+            let input = Day04::parse(&raw_input).unwrap();
+            let boards: Vec<_> = (boards_nums.iter())
+                .map(|nums| BingoBoard::from_input(nums.chunks(5).map(|l| l.to_vec()).collect()))
+                .collect();
+            prop_assert_eq!(&input, &(random_numbers, boards));
+            Day04::part1(&input).unwrap();
+            Day04::part2(&input).unwrap();
+        }
     }
 }
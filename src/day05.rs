@@ -1,38 +1,29 @@
 // day 05
 
-use std::collections::HashMap;
-
-use chumsky as c;
+use anyhow::Result as AnyResult;
 use chumsky::prelude::*;
 
-#[derive(Debug, Eq, PartialEq, Hash)]
-struct Point2D {
-    pub x: i32,
-    pub y: i32,
-}
-impl Point2D {
-    fn from_coords((x, y): (i32, i32)) -> Self {
-        Point2D { x, y }
-    }
-}
+use crate::grid::{Pos, SparseGrid};
+use crate::solution::{Example, Solution};
+use crate::{diagnostic, parsing, PartResult};
 
 #[derive(Debug, Eq, PartialEq)]
-struct VentLine {
-    start: Point2D,
-    end: Point2D,
+pub struct VentLine {
+    pub start: Pos,
+    pub end: Pos,
 }
 impl VentLine {
-    fn between_points((p1, p2): (Point2D, Point2D)) -> Self {
+    fn between_points((p1, p2): (Pos, Pos)) -> Self {
         VentLine { start: p1, end: p2 }
     }
 
-    fn is_horiz_or_vert(&self) -> bool {
+    pub fn is_horiz_or_vert(&self) -> bool {
         self.start.x == self.end.x || self.start.y == self.end.y
     }
 
     // NOTE: I don't like this 'imperative' way of doing this... But I can't wrap my head around
     //       an iterator-friendly way for now...
-    fn points(&self) -> Vec<Point2D> {
+    pub fn points(&self) -> Vec<Pos> {
         let mut points_on_vent = vec![];
 
         use std::cmp::Ordering;
@@ -50,50 +41,49 @@ impl VentLine {
         let (mut x, mut y) = (self.start.x, self.start.y);
         while x != self.end.x || y != self.end.y {
             // println!(" * ({}, {})", x, y);
-            points_on_vent.push(Point2D { x, y });
+            points_on_vent.push(Pos { x, y });
             x += x_increment;
             y += y_increment;
         }
         // println!(" * ({}, {})", x, y);
-        points_on_vent.push(Point2D { x, y }); // and last point!
+        points_on_vent.push(Pos { x, y }); // and last point!
 
         points_on_vent
     }
 }
 
-#[derive(Debug)]
-struct OceanMap {
-    known_vent_points: HashMap<Point2D, i32>,
+#[derive(Debug, Default)]
+pub struct OceanMap {
+    known_vent_points: SparseGrid<i32>,
 }
 impl OceanMap {
-    fn new() -> Self {
-        Self {
-            known_vent_points: HashMap::new(),
-        }
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    fn register_hydrothermal_vent(&mut self, vent: &VentLine) {
+    pub fn register_hydrothermal_vent(&mut self, vent: &VentLine) {
         for vent_point in vent.points() {
-            let nb_vents = self.known_vent_points.entry(vent_point).or_insert(0);
-            *nb_vents += 1;
+            *self.known_vent_points.get_or_default(vent_point) += 1;
         }
     }
 
-    fn count_dangerous_vent_points(&self) -> usize {
+    pub fn count_dangerous_vent_points(&self) -> usize {
         self.known_vent_points
-            .iter()
-            .filter(|(_, &nb_vents)| nb_vents >= 2)
+            .values()
+            .filter(|&&nb_vents| nb_vents >= 2)
             .count()
     }
 }
 
+// NOTE: The map is drawn from (0,0), not from the first vent, like the maps of the puzzle.
 impl std::fmt::Display for OceanMap {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let max_x = self.known_vent_points.keys().map(|p| p.x).max().unwrap();
-        let max_y = self.known_vent_points.keys().map(|p| p.y).max().unwrap();
-        for y in 0..=max_y {
-            for x in 0..=max_x {
-                match self.known_vent_points.get(&Point2D { x, y }) {
+        let Some((_, max)) = self.known_vent_points.bounds() else {
+            return Ok(());
+        };
+        for y in 0..=max.y {
+            for x in 0..=max.x {
+                match self.known_vent_points.get(Pos { x, y }) {
                     Some(x) => write!(f, "{}", x)?,
                     None => write!(f, ".")?,
                 };
@@ -107,41 +97,14 @@ impl std::fmt::Display for OceanMap {
 // Format:
 //   1,2 -> 3,4
 //   ...
-fn input_parser() -> impl Parser<char, Vec<VentLine>, Error = Simple<char>> {
-    let number = c::text::int(10).map(|s: String| s.parse().unwrap());
-    let point = (number.then_ignore(just(',')).then(number)).map(Point2D::from_coords);
+pub fn input_parser() -> impl Parser<char, Vec<VentLine>, Error = Simple<char>> {
+    let point = parsing::point();
     let arrow = just(" -> ").ignored();
     let ventline = (point.then_ignore(arrow).then(point)).map(VentLine::between_points);
-    ventline.separated_by(c::text::newline())
-}
-
-pub fn solve_part1(raw_input: &str) -> (usize, Option<usize>) {
-    let known_vents = input_parser().parse(raw_input).unwrap();
-
-    let mut map = OceanMap::new();
-    for vent in known_vents.into_iter().filter(|v| v.is_horiz_or_vert()) {
-        map.register_hydrothermal_vent(&vent);
-    }
-    // println!("{}", map);
-    (map.count_dangerous_vent_points(), Some(6283))
-}
-
-pub fn solve_part2(raw_input: &str) -> (usize, Option<usize>) {
-    let known_vents = input_parser().parse(raw_input).unwrap();
-
-    let mut map = OceanMap::new();
-    for vent in known_vents {
-        map.register_hydrothermal_vent(&vent);
-    }
-    // println!("{}", map);
-    (map.count_dangerous_vent_points(), Some(18864))
+    parsing::lines(ventline)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    static EXAMPLE_INPUT: &str = r#"
+static EXAMPLE_INPUT: &str = r#"
 0,9 -> 5,9
 8,0 -> 0,8
 9,4 -> 3,4
@@ -152,7 +115,48 @@ mod tests {
 3,4 -> 1,4
 0,0 -> 8,8
 5,5 -> 8,2
-    "#;
+"#;
+
+/// Crossing lines.
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Vec<VentLine>;
+    const NAME: &'static str = "day05";
+    const TITLE: &'static str = "Hydrothermal Venture";
+
+    fn parse(raw_input: &str) -> AnyResult<Self::Input> {
+        diagnostic::parse(input_parser(), raw_input)
+    }
+
+    fn part1(known_vents: &Self::Input) -> PartResult {
+        let mut map = OceanMap::new();
+        for vent in known_vents.iter().filter(|v| v.is_horiz_or_vert()) {
+            map.register_hydrothermal_vent(vent);
+        }
+        // println!("{}", map);
+        Ok(map.count_dangerous_vent_points().into())
+    }
+
+    fn part2(known_vents: &Self::Input) -> PartResult {
+        let mut map = OceanMap::new();
+        for vent in known_vents {
+            map.register_hydrothermal_vent(vent);
+        }
+        // println!("{}", map);
+        Ok(map.count_dangerous_vent_points().into())
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT, 5, 12)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Answer;
+    use proptest::prelude::*;
 
     // Part1 map:
     //
@@ -186,21 +190,51 @@ mod tests {
         assert_eq!(
             vent_lines.first(),
             Some(&VentLine {
-                start: Point2D { x: 0, y: 9 },
-                end: Point2D { x: 5, y: 9 }
+                start: Pos::new(0, 9),
+                end: Pos::new(5, 9)
             })
         );
     }
 
     #[test]
     fn test_example_part1() {
-        let (result, _) = solve_part1(EXAMPLE_INPUT.trim());
-        assert_eq!(result, 5);
+        let input = Day05::parse(EXAMPLE_INPUT).unwrap();
+        let result = Day05::part1(&input).unwrap();
+        assert_eq!(result, Answer::Int(5));
     }
 
     #[test]
     fn test_example_part2() {
-        let (result, _) = solve_part2(EXAMPLE_INPUT.trim());
-        assert_eq!(result, 12);
+        let input = Day05::parse(EXAMPLE_INPUT).unwrap();
+        let result = Day05::part2(&input).unwrap();
+        assert_eq!(result, Answer::Int(12));
+    }
+
+    #[test]
+    fn test_display_from_origin() {
+        let mut map = OceanMap::new();
+        map.register_hydrothermal_vent(&VentLine::between_points((Pos::new(1, 1), Pos::new(2, 1))));
+        assert_eq!(map.to_string(), "...\n.11\n");
+    }
+
+    /// Random horizontal, vertical or diagonal (at 45 degrees) lines.
+    fn ventline_strategy() -> impl Strategy<Value = VentLine> {
+        ((0..100, 0..100), (-1..=1, -1..=1), 0..50).prop_map(|((x, y), (dx, dy), len)| {
+            VentLine::between_points((Pos::new(x, y), Pos::new(x + dx * len, y + dy * len)))
+        })
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+        #[test]
+        fn prop_parse_and_solve(vents in prop::collection::vec(ventline_strategy(), 1..100)) {
+            let lines: Vec<_> = (vents.iter())
+                .map(|v| format!("{},{} -> {},{}", v.start.x, v.start.y, v.end.x, v.end.y))
+                .collect();
+            let input = Day05::parse(&lines.join("\n")).unwrap();
+            prop_assert_eq!(&input, &vents);
+            Day05::part1(&input).unwrap();
+            Day05::part2(&input).unwrap();
+        }
     }
 }
@@ -2,13 +2,17 @@
 
 use std::collections::HashMap;
 
-use chumsky as c;
+use anyhow::Result as AnyResult;
 use chumsky::prelude::*;
 
-type BirthStage = u8;
+use crate::cross::Rng;
+use crate::solution::{Example, Reference, Solution};
+use crate::{diagnostic, parsing, PartResult};
 
-#[derive(Debug)]
-struct Lanternfish {
+pub type BirthStage = u8;
+
+#[derive(Debug, Clone)]
+pub struct Lanternfish {
     pub days_before_childbirth: BirthStage,
 }
 impl std::fmt::Display for Lanternfish {
@@ -19,46 +23,15 @@ impl std::fmt::Display for Lanternfish {
 
 // Format:
 //   1,2,3,4 ...
-fn input_parser() -> impl Parser<char, Vec<Lanternfish>, Error = Simple<char>> {
-    let number = c::text::int(10).map(|s: String| s.parse().unwrap());
-    let lanternfish = number.map(|n| Lanternfish {
+pub fn input_parser() -> impl Parser<char, Vec<Lanternfish>, Error = Simple<char>> {
+    let lanternfish = parsing::unsigned().map(|n| Lanternfish {
         days_before_childbirth: n,
     });
-    lanternfish.separated_by(just(','))
-}
-
-pub fn solve_part1(raw_input: &str) -> (usize, Option<usize>) {
-    let mut fishes = input_parser().parse(raw_input).unwrap();
-    let run_for_days = 80;
-
-    // impl bete & mechante... (but slow and using lots of memory..)
-    #[allow(unused_variables)]
-    for day in 1..=run_for_days {
-        // Run simulation for that day
-        let mut nb_fish_birth = 0;
-        fishes.iter_mut().for_each(|fish| {
-            if fish.days_before_childbirth == 0 {
-                nb_fish_birth += 1;
-                fish.days_before_childbirth = 6;
-            } else {
-                fish.days_before_childbirth -= 1;
-            }
-        });
-        // Let new fishes be born!
-        for _ in 0..nb_fish_birth {
-            fishes.push(Lanternfish {
-                days_before_childbirth: 8,
-            });
-        }
-        // Print
-        // println!("After {:2} days, nb fishes: {}", day, fishes.len());
-    }
-
-    (fishes.len(), Some(391671))
+    parsing::comma_list(lanternfish)
 }
 
-#[derive(Debug)]
-struct LanternfishBirthSimulator {
+#[derive(Debug, Default)]
+pub struct LanternfishBirthSimulator {
     fish_count_by_birth_stage: HashMap<BirthStage, usize>,
 }
 impl LanternfishBirthSimulator {
@@ -90,7 +63,7 @@ impl LanternfishBirthSimulator {
 
     pub fn simulate_passing_day(&mut self) {
         // new day, reset counters
-        let last_state = std::mem::replace(&mut self.fish_count_by_birth_stage, HashMap::new());
+        let last_state = std::mem::take(&mut self.fish_count_by_birth_stage);
 
         // For all fish waiting to give birth, let them pass the day
         for birth_stage in 1..=8 {
@@ -106,44 +79,133 @@ impl LanternfishBirthSimulator {
     }
 }
 
-#[allow(unused_variables)]
-pub fn solve_part2(raw_input: &str) -> (usize, Option<usize>) {
-    let input_fishes = input_parser().parse(raw_input).unwrap();
-    // NOTE: The dummy impl doesn't work for 256 days, because even around 190 days, there are
-    // 7_172_256_393 fishes already, which means 7 Gigabytes of RAM used to store fishes!!!
-    // (given 1 fish stored in a byte)
-    let run_for_days = 256;
-    // We need another data structure for that fish simulation..
+static EXAMPLE_INPUT: &str = "3,4,3,1,2";
 
-    // IDEA: We don't actually care about the order of fishes, or even about the fishes at all.
-    // I could simply store the number of fishes at each day-before-birth stage, and simulate
-    // progress with it!
-    // ==> That's what I implemented in LanternfishBirthSimulator, works flawlessly :)
-    let mut simu = LanternfishBirthSimulator::from_fishes(&input_fishes);
+/// Recursive fish colony.
+pub struct Day06;
 
-    for day in 1..=run_for_days {
-        simu.simulate_passing_day();
-        // println!("After {:2} days, nb fishes: {}", day, simu.fish_count());
+impl Day06 {
+    /// Counts the descendants of each fish one by one, a reference for `part1`.
+    fn part1_by_descendants(input_fishes: &[Lanternfish]) -> PartResult {
+        // Number of fishes in `days` days from a fish that gives birth in `days_before_childbirth`
+        // days: itself & all the fishes it gives birth to (every 7 days), with their descendants.
+        fn count_fishes(days_before_childbirth: i32, days: i32) -> usize {
+            let births = (days_before_childbirth + 1..=days).step_by(7);
+            1 + births.map(|day| count_fishes(8, days - day)).sum::<usize>()
+        }
+        let count = (input_fishes.iter())
+            .map(|fish| count_fishes(fish.days_before_childbirth as i32, 80))
+            .sum::<usize>();
+        Ok(count.into())
     }
+}
 
-    (simu.fish_count(), Some(1_754_000_560_399)) // that's a lot...
+impl Solution for Day06 {
+    type Input = Vec<Lanternfish>;
+    const NAME: &'static str = "day06";
+    const TITLE: &'static str = "Lanternfish";
+
+    fn parse(raw_input: &str) -> AnyResult<Self::Input> {
+        diagnostic::parse(input_parser(), raw_input)
+    }
+
+    fn part1(input_fishes: &Self::Input) -> PartResult {
+        let mut fishes = input_fishes.clone();
+        let run_for_days = 80;
+
+        // impl bete & mechante... (but slow and using lots of memory..)
+        #[allow(unused_variables)]
+        for day in 1..=run_for_days {
+            // Run simulation for that day
+            let mut nb_fish_birth = 0;
+            fishes.iter_mut().for_each(|fish| {
+                if fish.days_before_childbirth == 0 {
+                    nb_fish_birth += 1;
+                    fish.days_before_childbirth = 6;
+                } else {
+                    fish.days_before_childbirth -= 1;
+                }
+            });
+            // Let new fishes be born!
+            for _ in 0..nb_fish_birth {
+                fishes.push(Lanternfish {
+                    days_before_childbirth: 8,
+                });
+            }
+            // Print
+            // println!("After {:2} days, nb fishes: {}", day, fishes.len());
+        }
+
+        Ok(fishes.len().into())
+    }
+
+    #[allow(unused_variables)]
+    fn part2(input_fishes: &Self::Input) -> PartResult {
+        // NOTE: The dummy impl doesn't work for 256 days, because even around 190 days, there are
+        // 7_172_256_393 fishes already, which means 7 Gigabytes of RAM used to store fishes!!!
+        // (given 1 fish stored in a byte)
+        let run_for_days = 256;
+        // We need another data structure for that fish simulation..
+
+        // IDEA: We don't actually care about the order of fishes, or even about the fishes at all.
+        // I could simply store the number of fishes at each day-before-birth stage, and simulate
+        // progress with it!
+        // ==> That's what I implemented in LanternfishBirthSimulator, works flawlessly :)
+        let mut simu = LanternfishBirthSimulator::from_fishes(input_fishes);
+
+        for day in 1..=run_for_days {
+            simu.simulate_passing_day();
+            // println!("After {:2} days, nb fishes: {}", day, simu.fish_count());
+        }
+
+        Ok(simu.fish_count().into())
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT, 5934, 26_984_457_539_i64)]
+    }
+
+    fn references() -> Vec<(u8, Reference<Self::Input>)> {
+        vec![(1, |fishes| Self::part1_by_descendants(fishes))]
+    }
+
+    /// Fishes at random stages, like in the puzzle input.
+    fn generate_input(size: usize, rng: &mut Rng) -> Option<String> {
+        let stages: Vec<_> = (0..size).map(|_| rng.range(1..6).to_string()).collect();
+        Some(stages.join(","))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    static EXAMPLE_INPUT: &str = "3,4,3,1,2";
+    use crate::Answer;
+    use proptest::prelude::*;
 
     #[test]
     fn test_example_part1() {
-        let (result, _) = solve_part1(EXAMPLE_INPUT);
-        assert_eq!(result, 5934);
+        let input = Day06::parse(EXAMPLE_INPUT).unwrap();
+        let result = Day06::part1(&input).unwrap();
+        assert_eq!(result, Answer::Int(5934));
     }
 
     #[test]
     fn test_example_part2() {
-        let (result, _) = solve_part2(EXAMPLE_INPUT);
-        assert_eq!(result, 26_984_457_539);
+        let input = Day06::parse(EXAMPLE_INPUT).unwrap();
+        let result = Day06::part2(&input).unwrap();
+        assert_eq!(result, Answer::Int(26_984_457_539));
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+        #[test]
+        fn prop_parse_and_solve(stages in prop::collection::vec(0..=8_u8, 1..30)) {
+            let raw_input = (stages.iter()).map(|s| s.to_string()).collect::<Vec<_>>().join(",");
+            let input = Day06::parse(&raw_input).unwrap();
+            let parsed_stages: Vec<_> = input.iter().map(|f| f.days_before_childbirth).collect();
+            prop_assert_eq!(&parsed_stages, &stages);
+            Day06::part1(&input).unwrap();
+            Day06::part2(&input).unwrap();
+        }
     }
 }
@@ -1,67 +1,139 @@
 // day 06
 
-use chumsky as c;
+use anyhow::Result as AnyResult;
 use chumsky::prelude::*;
 
+use crate::cross::Rng;
+use crate::solution::{Example, Reference, Solution};
+use crate::{diagnostic, parsing, PartResult};
+
 // Format:
 //   1,2,3,4 ...
-fn input_parser() -> impl Parser<char, Vec<i32>, Error = Simple<char>> {
-    let number = c::text::int(10).map(|s: String| s.parse().unwrap());
-    number.separated_by(just(','))
+pub fn input_parser() -> impl Parser<char, Vec<i32>, Error = Simple<char>> {
+    parsing::comma_list(parsing::unsigned())
 }
 
-pub fn solve_part1(raw_input: &str) -> (usize, Option<usize>) {
-    let input = input_parser().parse(raw_input).unwrap();
-    let min = input.iter().min().cloned().unwrap();
-    let max = input.iter().max().cloned().unwrap();
+static EXAMPLE_INPUT: &str = "16,1,2,0,4,2,7,1,2,14";
 
-    // Brute force impl..
-    // For each horiz position (hpos), sum the distances of that hpos to all crabs
-    // Then find the smallest sum of distances.
-    let res = (min..=max)
-        .map(|hpos| input.iter().map(|x| (x - hpos).abs()).sum::<i32>())
-        .min()
-        .unwrap();
+/// Efficient crab movements.
+pub struct Day07;
 
-    (res as usize, Some(356179))
+impl Day07 {
+    /// Moves the crabs one step at a time to every position, a reference for both parts (with
+    /// the fuel used by the n-th step of a crab).
+    fn fuel_step_by_step(input: &[i32], fuel_for_step: fn(i64) -> i64) -> PartResult {
+        let max = input.iter().max().cloned().unwrap();
+        let res = (0..=max)
+            .map(|hpos| {
+                let mut fuel = 0;
+                for &crab in input {
+                    let (mut pos, mut step) = (crab, 1);
+                    while pos != hpos {
+                        pos += (hpos - pos).signum();
+                        fuel += fuel_for_step(step);
+                        step += 1;
+                    }
+                }
+                fuel
+            })
+            .min()
+            .unwrap();
+        Ok(res.into())
+    }
 }
 
-pub fn solve_part2(raw_input: &str) -> (usize, Option<usize>) {
-    let input = input_parser().parse(raw_input).unwrap();
-    let min = input.iter().min().cloned().unwrap();
-    let max = input.iter().max().cloned().unwrap();
-
-    // Brute force impl..
-    // For each horiz position (hpos), sum the fuel used for distance (by 1+2+3...) of that hpos to all crabs
-    // Then find the smallest sum of distances.
-    let res = (min..=max)
-        .map(|hpos| {
-            input
-                .iter()
-                .map(|x| (0..=(x - hpos).abs()).sum::<i32>())
-                .sum::<i32>()
-        })
-        .min()
-        .unwrap();
-
-    (res as usize, Some(99788435))
+impl Solution for Day07 {
+    type Input = Vec<i32>;
+    const NAME: &'static str = "day07";
+    const TITLE: &'static str = "The Treachery of Whales";
+
+    fn parse(raw_input: &str) -> AnyResult<Self::Input> {
+        diagnostic::parse(input_parser(), raw_input)
+    }
+
+    fn part1(input: &Self::Input) -> PartResult {
+        let min = input.iter().min().cloned().unwrap();
+        let max = input.iter().max().cloned().unwrap();
+
+        // Brute force impl..
+        // For each horiz position (hpos), sum the distances of that hpos to all crabs
+        // Then find the smallest sum of distances.
+        let res = (min..=max)
+            .map(|hpos| input.iter().map(|x| (x - hpos).abs()).sum::<i32>())
+            .min()
+            .unwrap();
+
+        Ok(res.into())
+    }
+
+    fn part2(input: &Self::Input) -> PartResult {
+        let min = input.iter().min().cloned().unwrap();
+        let max = input.iter().max().cloned().unwrap();
+
+        // Brute force impl..
+        // For each horiz position (hpos), sum the fuel used for distance (by 1+2+3...) of that hpos to all crabs
+        // Then find the smallest sum of distances.
+        let res = (min..=max)
+            .map(|hpos| {
+                input
+                    .iter()
+                    .map(|x| (0..=(x - hpos).abs()).sum::<i32>())
+                    .sum::<i32>()
+            })
+            .min()
+            .unwrap();
+
+        Ok(res.into())
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT, 37, 168)]
+    }
+
+    fn references() -> Vec<(u8, Reference<Self::Input>)> {
+        vec![
+            (1, |input| Self::fuel_step_by_step(input, |_| 1)),
+            (2, |input| Self::fuel_step_by_step(input, |step| step)),
+        ]
+    }
+
+    /// Crabs at random positions, more spread out with more crabs.
+    fn generate_input(size: usize, rng: &mut Rng) -> Option<String> {
+        let max_pos = 10 * size as u64;
+        let positions: Vec<_> = (0..size).map(|_| rng.range(0..max_pos).to_string()).collect();
+        Some(positions.join(","))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    static EXAMPLE_INPUT: &str = "16,1,2,0,4,2,7,1,2,14";
+    use crate::Answer;
+    use proptest::prelude::*;
 
     #[test]
     fn test_example_part1() {
-        let (result, _) = solve_part1(EXAMPLE_INPUT);
-        assert_eq!(result, 37);
+        let input = Day07::parse(EXAMPLE_INPUT).unwrap();
+        let result = Day07::part1(&input).unwrap();
+        assert_eq!(result, Answer::Int(37));
     }
 
     #[test]
     fn test_example_part2() {
-        let (result, _) = solve_part2(EXAMPLE_INPUT);
-        assert_eq!(result, 168);
+        let input = Day07::parse(EXAMPLE_INPUT).unwrap();
+        let result = Day07::part2(&input).unwrap();
+        assert_eq!(result, Answer::Int(168));
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+        #[test]
+        fn prop_parse_and_solve(positions in prop::collection::vec(0..300_i32, 1..50)) {
+            let raw_input = (positions.iter()).map(|p| p.to_string()).collect::<Vec<_>>().join(",");
+            let input = Day07::parse(&raw_input).unwrap();
+            prop_assert_eq!(&input, &positions);
+            Day07::part1(&input).unwrap();
+            Day07::part2(&input).unwrap();
+        }
     }
 }
@@ -2,16 +2,19 @@
 
 use std::collections::{HashMap, HashSet};
 
-use chumsky as c;
+use anyhow::Result as AnyResult;
 use chumsky::prelude::*;
 use lazy_static::lazy_static;
 
-type SignalPattern = HashSet<char>;
+use crate::solution::{Example, Solution};
+use crate::{diagnostic, parsing, PartResult};
+
+pub type SignalPattern = HashSet<char>;
 
 #[derive(Debug, Clone)]
-struct InputLine {
-    init: Vec<SignalPattern>,
-    output: Vec<SignalPattern>,
+pub struct InputLine {
+    pub init: Vec<SignalPattern>,
+    pub output: Vec<SignalPattern>,
 }
 
 fn fmt_signals(sigs: &SignalPattern) -> String {
@@ -22,11 +25,11 @@ fn fmt_signals(sigs: &SignalPattern) -> String {
 //   ceb bgfdea febgc ec eadcgfb eagbcd fcdebg dcef gafbc egdbf | fdbgec fedbg gdabefc gefbd
 //   af cegdabf cfdge ecdbfg dcfga edafgc cfa cabedf gdbac afge | cgdab bcagd badecgf fa
 //   ...
-fn input_parser() -> impl Parser<char, Vec<InputLine>, Error = Simple<char>> {
+pub fn input_parser() -> impl Parser<char, Vec<InputLine>, Error = Simple<char>> {
     let signal_pattern = one_of("abcdefg")
         .repeated()
         .at_least(1)
-        .map(|chars| HashSet::from_iter(chars));
+        .map(HashSet::from_iter);
     let patterns = signal_pattern.separated_by(just(' ')).at_least(1);
     let input_line = patterns
         // NOTE: clone() is necessary because parsers are moved when combined,
@@ -36,20 +39,7 @@ fn input_parser() -> impl Parser<char, Vec<InputLine>, Error = Simple<char>> {
         .then_ignore(just(" | "))
         .then(patterns)
         .map(|(init, output)| InputLine { init, output });
-    input_line.separated_by(c::text::newline())
-}
-
-pub fn solve_part1(raw_input: &str) -> (usize, Option<usize>) {
-    let input_lines = input_parser().parse(raw_input).unwrap();
-
-    let res = input_lines
-        .into_iter()
-        .map(|l| l.output)
-        .flatten()
-        .filter(|sig_pattern| [2, 3, 4, 7].contains(&sig_pattern.len()))
-        .count();
-
-    (res, Some(349))
+    parsing::lines(input_line)
 }
 
 lazy_static! {
@@ -67,8 +57,8 @@ lazy_static! {
     ];
 }
 
-#[derive(Debug)]
-struct WireMapper {
+#[derive(Debug, Default)]
+pub struct WireMapper {
     digit_to_signal_pattern: HashMap<u32, SignalPattern>,
 }
 impl WireMapper {
@@ -86,8 +76,7 @@ impl WireMapper {
     }
 
     pub fn guess_from_init(&mut self, init_patterns: &[SignalPattern]) {
-        let mut patterns_left_to_guess: Vec<SignalPattern> =
-            init_patterns.iter().cloned().collect();
+        let mut patterns_left_to_guess: Vec<SignalPattern> = init_patterns.to_vec();
 
         let pattern_for_1 = Self::pop_pattern_with_len(&mut patterns_left_to_guess, 2);
         let pattern_for_4 = Self::pop_pattern_with_len(&mut patterns_left_to_guess, 4);
@@ -143,7 +132,7 @@ impl WireMapper {
     }
 }
 
-fn guess_and_resolve_output(input_line: InputLine) -> usize {
+fn guess_and_resolve_output(input_line: &InputLine) -> usize {
     let mut sig_mapper = WireMapper::new();
     sig_mapper.guess_from_init(&input_line.init);
     let output_digits: Vec<u32> = input_line
@@ -163,18 +152,7 @@ fn guess_and_resolve_output(input_line: InputLine) -> usize {
     output_number as usize
 }
 
-pub fn solve_part2(raw_input: &str) -> (usize, Option<usize>) {
-    let input_lines = input_parser().parse(raw_input).unwrap();
-
-    let sum_all_outputs = input_lines.into_iter().map(guess_and_resolve_output).sum();
-    (sum_all_outputs, Some(1070957))
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    static EXAMPLE_INPUT: &str = r#"
+static EXAMPLE_INPUT: &str = r#"
 be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
 edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
 fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
@@ -187,23 +165,122 @@ egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
 gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
 "#;
 
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Vec<InputLine>;
+    const NAME: &'static str = "day08";
+    const TITLE: &'static str = "Seven Segment Search";
+
+    fn parse(raw_input: &str) -> AnyResult<Self::Input> {
+        diagnostic::parse(input_parser(), raw_input)
+    }
+
+    fn part1(input_lines: &Self::Input) -> PartResult {
+        let res = input_lines
+            .iter()
+            .flat_map(|l| &l.output)
+            .filter(|sig_pattern| [2, 3, 4, 7].contains(&sig_pattern.len()))
+            .count();
+
+        Ok(res.into())
+    }
+
+    fn part2(input_lines: &Self::Input) -> PartResult {
+        let sum_all_outputs: usize = input_lines.iter().map(guess_and_resolve_output).sum();
+        Ok(sum_all_outputs.into())
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![
+            Example::new(EXAMPLE_INPUT, 26, 61229),
+            Example {
+                input: "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf",
+                part1: None,
+                part2: Some(5353.into()),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Answer;
+    use proptest::prelude::*;
+
     #[test]
     fn test_example_part1() {
-        let (result, _) = solve_part1(EXAMPLE_INPUT.trim());
-        assert_eq!(result, 26);
+        let input = Day08::parse(EXAMPLE_INPUT).unwrap();
+        let result = Day08::part1(&input).unwrap();
+        assert_eq!(result, Answer::Int(26));
     }
 
     #[test]
     fn test_example_part2() {
-        let (result, _) = solve_part2(EXAMPLE_INPUT.trim());
-        assert_eq!(result, 61229);
+        let input = Day08::parse(EXAMPLE_INPUT).unwrap();
+        let result = Day08::part2(&input).unwrap();
+        assert_eq!(result, Answer::Int(61229));
     }
 
     #[test]
     fn test_short_example_part2() {
-        let (result, _) = solve_part2(
+        let input = Day08::parse(
             "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf",
-        );
-        assert_eq!(result, 5353);
+        )
+        .unwrap();
+        let result = Day08::part2(&input).unwrap();
+        assert_eq!(result, Answer::Int(5353));
+    }
+
+    /// A random display: the wires mixed up, its 10 digits in a random order, and its 4 output
+    /// digits.
+    fn display_strategy() -> impl Strategy<Value = (Vec<char>, Vec<usize>, Vec<usize>)> {
+        (
+            Just("abcdefg".chars().collect::<Vec<_>>()).prop_shuffle(),
+            Just((0..10).collect::<Vec<_>>()).prop_shuffle(),
+            prop::collection::vec(0..10_usize, 4),
+        )
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+        #[test]
+        fn prop_parse_and_solve(displays in prop::collection::vec(display_strategy(), 1..20)) {
+            let fmt_patterns = |patterns: &[SignalPattern]| -> String {
+                let patterns: Vec<String> = (patterns.iter())
+                    .map(|pattern| pattern.iter().collect())
+                    .collect();
+                patterns.join(" ")
+            };
+            let (mut lines, mut expected_lines) = (vec![], vec![]);
+            let mut expected_sum = 0;
+            for (wires, init_digits, output_digits) in &displays {
+                let pattern_of = |digit: &usize| -> SignalPattern {
+                    (DIGITS_SEGMENTS[*digit].iter())
+                        .map(|seg| wires[(*seg as u8 - b'a') as usize])
+                        .collect()
+                };
+                let line = InputLine {
+                    init: init_digits.iter().map(pattern_of).collect(),
+                    output: output_digits.iter().map(pattern_of).collect(),
+                };
+                lines.push(format!(
+                    "{} | {}",
+                    fmt_patterns(&line.init),
+                    fmt_patterns(&line.output)
+                ));
+                expected_lines.push(line);
+                expected_sum += output_digits.iter().fold(0, |acc, digit| acc * 10 + digit);
+            }
+            let input = Day08::parse(&lines.join("\n")).unwrap();
+            for (parsed, expected) in input.iter().zip(&expected_lines) {
+                prop_assert_eq!(&parsed.init, &expected.init);
+                prop_assert_eq!(&parsed.output, &expected.output);
+            }
+            prop_assert_eq!(input.len(), expected_lines.len());
+            Day08::part1(&input).unwrap();
+            prop_assert_eq!(Day08::part2(&input).unwrap(), Answer::Int(expected_sum as i64));
+        }
     }
 }
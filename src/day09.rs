@@ -2,72 +2,43 @@
 
 use std::collections::HashSet;
 
-use chumsky as c;
+use anyhow::{Context, Result as AnyResult};
 use chumsky::prelude::*;
 
+use crate::grid::{Grid, Pos};
+use crate::solution::{Example, Solution};
+use crate::{diagnostic, parsing, PartResult};
+
 // Format:
 //   2199943210
 //   3987894921
 //   ...
-fn input_parser() -> impl Parser<char, HeightMap, Error = Simple<char>> {
-    let digit = one_of("0123456789").map(|chr: char| chr.to_digit(10).unwrap_or(0));
-    let heights_line = digit.repeated().at_least(1);
-    heights_line
-        .separated_by(c::text::newline())
-        .map(HeightMap::new)
-}
-
-#[derive(Clone, Copy, Eq, PartialEq, Hash)]
-struct PosXY {
-    pub x: i32,
-    pub y: i32,
-}
-impl PosXY {
-    pub fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-}
-// impl a oneline Debug representation of the position :)
-impl std::fmt::Debug for PosXY {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // NOTE: to escape braces in format strings, it must be repeated.
-        // ref: https://doc.rust-lang.org/std/fmt/index.html#escaping
-        write!(f, "PosXY {{ x: {}, y: {} }}", &self.x, &self.y)
-    }
+pub fn input_parser() -> impl Parser<char, HeightMap, Error = Simple<char>> {
+    parsing::digit_grid().map(HeightMap::new)
 }
 
 #[derive(Debug)]
-struct HeightMap {
-    grid: Vec<Vec<u32>>,
+pub struct HeightMap {
+    grid: Grid<u32>,
 }
 impl HeightMap {
-    pub fn new(grid: Vec<Vec<u32>>) -> Self {
+    pub fn new(grid: Grid<u32>) -> Self {
         Self { grid }
     }
 
-    pub fn get_at(&self, pos: &PosXY) -> Option<&u32> {
-        self.grid
-            .get(pos.y as usize)
-            .and_then(|grid_line| grid_line.get(pos.x as usize))
+    pub fn get_at(&self, pos: &Pos) -> Option<&u32> {
+        self.grid.get(*pos)
     }
 
     // NOTE: `+ '_` on the return value declares that the return type captures data from `self` and
     // thus they must have the same lifetime.
     // Basically means `and it has the same lifetime as self` (necessary when not obvious)
-    pub fn iter_heights(&self) -> impl Iterator<Item = (PosXY, u32)> + '_ {
-        self.grid.iter().enumerate().flat_map(|(pos_y, line)| {
-            line.iter()
-                .enumerate()
-                // NOTE: move is necessary to force the closure to take ownership of `pos_y`
-                // (and `line`, but it's a ref so no problem)
-                .map(move |(pos_x, height)| {
-                    let pos = PosXY::new(pos_x as i32, pos_y as i32);
-                    (pos, *height)
-                })
-        })
+    pub fn iter_heights(&self) -> impl Iterator<Item = (Pos, u32)> + '_ {
+        self.grid.iter().map(|(pos, height)| (pos, *height))
     }
 
-    pub fn iter_lowest_heights_points(&self) -> impl Iterator<Item = (PosXY, u32)> + '_ {
+    pub fn iter_lowest_heights_points(&self) -> impl Iterator<Item = (Pos, u32)> + '_ {
+        // keep only the points with all their neighbours greater than 'current_height'
         self.iter_heights().filter(|(pos, current_height)| {
             self.iter_neighbours_heights_points(pos)
                 .all(|(_pos, height)| height > *current_height)
@@ -76,97 +47,126 @@ impl HeightMap {
 
     pub fn iter_neighbours_heights_points(
         &self,
-        pos: &PosXY,
-    ) -> impl Iterator<Item = (PosXY, u32)> + '_ {
-        let neighbours_positions = [
-            PosXY::new(pos.x - 1, pos.y), // left
-            PosXY::new(pos.x + 1, pos.y), // right
-            PosXY::new(pos.x, pos.y - 1), // up
-            PosXY::new(pos.x, pos.y + 1), // down
-        ];
-        // keep only this item if all its neighbours are greater than 'current_height'
-        neighbours_positions
-            .into_iter()
-            .filter_map(|pos| self.get_at(&pos).and_then(|height| Some((pos, *height))))
+        pos: &Pos,
+    ) -> impl Iterator<Item = (Pos, u32)> + '_ {
+        self.grid
+            .neighbours4(*pos)
+            .map(|(pos, height)| (pos, *height))
     }
 
-    pub fn get_basin_size_from(&self, from_pos: &PosXY) -> usize {
+    pub fn get_basin_size_from(&self, from_pos: &Pos) -> usize {
         let mut points_in_basin = HashSet::new();
         points_in_basin.insert(*from_pos);
         self.accumulate_points_in_basin(from_pos, &mut points_in_basin);
         points_in_basin.len()
     }
 
-    fn accumulate_points_in_basin(&self, from_pos: &PosXY, points_in_basin: &mut HashSet<PosXY>) {
+    fn accumulate_points_in_basin(&self, from_pos: &Pos, points_in_basin: &mut HashSet<Pos>) {
         for (neigh_pos, height) in self.iter_neighbours_heights_points(from_pos) {
-            if height < 9 {
-                if points_in_basin.insert(neigh_pos) {
-                    // `neigh_pos` was inserted, so it wasn't known to be in the basin yet and we
-                    // need to recurse to check its neighbours.
-                    self.accumulate_points_in_basin(&neigh_pos, points_in_basin);
-                }
+            if height < 9 && points_in_basin.insert(neigh_pos) {
+                // `neigh_pos` was inserted, so it wasn't known to be in the basin yet and we
+                // need to recurse to check its neighbours.
+                self.accumulate_points_in_basin(&neigh_pos, points_in_basin);
             }
         }
     }
 }
 
-pub fn solve_part1(raw_input: &str) -> (usize, Option<usize>) {
-    let heightmap = input_parser().parse(raw_input).unwrap();
+static EXAMPLE_INPUT: &str = r#"
+2199943210
+3987894921
+9856789892
+8767896789
+9899965678
+"#;
+
+/// Find low points & basins in a heightmap.
+pub struct Day09;
 
-    let lowest_points = heightmap.iter_lowest_heights_points();
-    let risk_levels = lowest_points.map(|(_pos, height)| 1 + height);
+impl Solution for Day09 {
+    type Input = HeightMap;
+    const NAME: &'static str = "day09";
+    const TITLE: &'static str = "Smoke Basin";
 
-    (risk_levels.sum::<u32>() as usize, Some(541))
-}
+    fn parse(raw_input: &str) -> AnyResult<Self::Input> {
+        diagnostic::parse(input_parser(), raw_input)
+    }
 
-pub fn solve_part2(raw_input: &str) -> (usize, Option<usize>) {
-    // NOTE: This implementation is enough for this AoC, but it's not perfect:
-    //   => If 2 lowest points are part of the same basin, there will be 2 basins counted
-    //      instead of 1.
-
-    let heightmap = input_parser().parse(raw_input).unwrap();
-
-    let lowest_points = heightmap.iter_lowest_heights_points();
-    let basin_sizes = lowest_points.map(|(pos, _height)| heightmap.get_basin_size_from(&pos));
-
-    let basin_sizes_sorted = {
-        let mut buffer = basin_sizes.collect::<Vec<_>>();
-        buffer.sort();
-        buffer.reverse(); // to have biggest first
-        buffer
-    };
-    // dbg!(&basin_sizes_sorted);
-
-    let result = basin_sizes_sorted
-        .into_iter()
-        .take(3)
-        .reduce(|a, b| a * b)
-        // necessary as reduce can theorically return None (but not here) if not enough values.
-        .unwrap();
-    (result, Some(847504))
+    fn part1(heightmap: &Self::Input) -> PartResult {
+        let lowest_points = heightmap.iter_lowest_heights_points();
+        let risk_levels = lowest_points.map(|(_pos, height)| 1 + height);
+
+        Ok(risk_levels.sum::<u32>().into())
+    }
+
+    fn part2(heightmap: &Self::Input) -> PartResult {
+        // NOTE: This implementation is enough for this AoC, but it's not perfect:
+        //   => If 2 lowest points are part of the same basin, there will be 2 basins counted
+        //      instead of 1.
+
+        let lowest_points = heightmap.iter_lowest_heights_points();
+        let basin_sizes = lowest_points.map(|(pos, _height)| heightmap.get_basin_size_from(&pos));
+
+        let basin_sizes_sorted = {
+            let mut buffer = basin_sizes.collect::<Vec<_>>();
+            buffer.sort();
+            buffer.reverse(); // to have biggest first
+            buffer
+        };
+        // dbg!(&basin_sizes_sorted);
+
+        let result = basin_sizes_sorted
+            .into_iter()
+            .take(3)
+            .reduce(|a, b| a * b)
+            // necessary as reduce returns None when there are no values (a flat heightmap has
+            // no low points).
+            .context("No low points in the heightmap")?;
+        Ok(result.into())
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT, 15, 1134)]
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    static EXAMPLE_INPUT: &str = r#"
-2199943210
-3987894921
-9856789892
-8767896789
-9899965678
-"#;
+    use crate::Answer;
+    use proptest::prelude::*;
 
     #[test]
     fn test_example_part1() {
-        let (result, _) = solve_part1(EXAMPLE_INPUT.trim());
-        assert_eq!(result, 15);
+        let input = Day09::parse(EXAMPLE_INPUT).unwrap();
+        let result = Day09::part1(&input).unwrap();
+        assert_eq!(result, Answer::Int(15));
     }
 
     #[test]
     fn test_example_part2() {
-        let (result, _) = solve_part2(EXAMPLE_INPUT.trim());
-        assert_eq!(result, 1134);
+        let input = Day09::parse(EXAMPLE_INPUT).unwrap();
+        let result = Day09::part2(&input).unwrap();
+        assert_eq!(result, Answer::Int(1134));
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+        #[test]
+        fn prop_parse_and_solve(
+            rows in (1..20_usize, 1..20_usize).prop_flat_map(|(width, height)| {
+                prop::collection::vec(prop::collection::vec(0..=9_u32, width), height)
+            })
+        ) {
+            let lines: Vec<String> = (rows.iter())
+                .map(|row| row.iter().map(|height| height.to_string()).collect())
+                .collect();
+            let input = Day09::parse(&lines.join("\n")).unwrap();
+            prop_assert_eq!(&input.grid, &Grid::from_rows(rows).unwrap());
+            Day09::part1(&input).unwrap();
+            // NOTE: A flat heightmap has no low points, and gives an error.
+            let has_low_points = input.iter_lowest_heights_points().next().is_some();
+            prop_assert_eq!(Day09::part2(&input).is_ok(), has_low_points);
+        }
     }
 }
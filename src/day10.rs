@@ -1,9 +1,12 @@
 // Day 10
 
-use chumsky as c;
+use anyhow::{bail, Result as AnyResult};
 use chumsky::prelude::*;
 
-enum Symbol {
+use crate::solution::{Example, Solution};
+use crate::{diagnostic, parsing, PartResult};
+
+pub enum Symbol {
     Open(char),
     Close(char),
 }
@@ -49,10 +52,10 @@ impl std::fmt::Debug for Symbol {
 //   [({(<(())[]>[[{[]{<()<>>
 //   [(()[<>])]({[<{<<[]>>(
 //   ...
-fn input_parser() -> impl Parser<char, Vec<Vec<Symbol>>, Error = Simple<char>> {
+pub fn input_parser() -> impl Parser<char, Vec<Vec<Symbol>>, Error = Simple<char>> {
     let token = one_of("([{<>}])").map(Symbol::from_char);
     let line = token.repeated().at_least(1);
-    line.separated_by(c::text::newline())
+    parsing::lines(line)
 }
 
 #[derive(Debug)]
@@ -69,7 +72,7 @@ fn analyze_line(line: &[Symbol]) -> Report {
             Symbol::Open(chr) => opener_stack.push(*chr),
             Symbol::Close(close_chr) => {
                 if let Some(last_open_chr) = opener_stack.pop() {
-                    if Symbol::matching_pair(&close_chr) == last_open_chr {
+                    if Symbol::matching_pair(close_chr) == last_open_chr {
                         // continue
                     } else {
                         return Report::Corrupted(*close_chr);
@@ -92,34 +95,6 @@ fn analyze_line(line: &[Symbol]) -> Report {
     }
 }
 
-pub fn solve_part1(raw_input: &str) -> (usize, Option<usize>) {
-    let lines = input_parser().parse(raw_input).unwrap();
-
-    // for line in &lines {
-    //     let line_str = line.iter().map(Symbol::to_char).collect::<String>();
-    //     println!("Line '{}' is {:?}", line_str, analyze_line(&line));
-    // }
-
-    let corruption_score: usize = lines
-        .iter()
-        .map(|line| analyze_line(line))
-        // keep only Corrupted entires
-        .filter(|rep| matches!(rep, Report::Corrupted(..)))
-        .map(|rep| {
-            let syntax_error_points = match rep {
-                Report::Corrupted(')') => 3,
-                Report::Corrupted(']') => 57,
-                Report::Corrupted('}') => 1197,
-                Report::Corrupted('>') => 25137,
-                _ => unreachable!(), // We kept only Corrupted variants
-            };
-            syntax_error_points
-        })
-        .sum();
-
-    (corruption_score, Some(168417))
-}
-
 fn closing_char_to_completion_score(chr: char) -> usize {
     match chr {
         ')' => 1,
@@ -130,48 +105,7 @@ fn closing_char_to_completion_score(chr: char) -> usize {
     }
 }
 
-pub fn solve_part2(raw_input: &str) -> (usize, Option<usize>) {
-    let lines = input_parser().parse(raw_input).unwrap();
-
-    let mut lines_completion_scores = lines
-        .iter()
-        .map(|line| analyze_line(line))
-        .filter_map(|rep| match rep {
-            Report::Incomplete { missing_chars } => {
-                let completion_score: usize = missing_chars.iter().fold(0, |acc, chr| {
-                    acc * 5 + closing_char_to_completion_score(*chr)
-                });
-                Some((missing_chars, completion_score))
-            }
-            _ => None,
-        })
-        .collect::<Vec<_>>();
-
-    lines_completion_scores.sort_by_key(|(_, score)| *score);
-
-    // for (missing_chars, compl_score) in &lines_completion_scores {
-    //     let chars_str = missing_chars.iter().collect::<String>();
-    //     println!(
-    //         "Missing chars '{}', completion score: {}",
-    //         chars_str, compl_score
-    //     );
-    // }
-
-    // Keep only the scores
-    let lines_completion_scores = lines_completion_scores
-        .iter()
-        .map(|(_, score)| score)
-        .collect::<Vec<_>>();
-
-    let mid_index = ((lines_completion_scores.len() - 1) as f64 / 2_f64).ceil() as usize;
-    (*lines_completion_scores[mid_index], Some(2802519786))
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    static EXAMPLE_INPUT: &str = r#"
+static EXAMPLE_INPUT: &str = r#"
 [({(<(())[]>[[{[]{<()<>>
 [(()[<>])]({[<{<<[]>>(
 {([(<{}[<>[]}>{[]{[(<()>
@@ -184,15 +118,139 @@ mod tests {
 <{([{{}}[<[[[<>{}]]]>[]]
 "#;
 
+/// Find unbalanced brackets: `[}(])>>`.
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Vec<Symbol>>;
+    const NAME: &'static str = "day10";
+    const TITLE: &'static str = "Syntax Scoring";
+
+    fn parse(raw_input: &str) -> AnyResult<Self::Input> {
+        diagnostic::parse(input_parser(), raw_input)
+    }
+
+    fn part1(lines: &Self::Input) -> PartResult {
+        // for line in &lines {
+        //     let line_str = line.iter().map(Symbol::to_char).collect::<String>();
+        //     println!("Line '{}' is {:?}", line_str, analyze_line(&line));
+        // }
+
+        let corruption_score: usize = lines
+            .iter()
+            .map(|line| analyze_line(line))
+            // keep only Corrupted entires
+            .filter(|rep| matches!(rep, Report::Corrupted(..)))
+            .map(|rep| {
+                match rep {
+                    Report::Corrupted(')') => 3,
+                    Report::Corrupted(']') => 57,
+                    Report::Corrupted('}') => 1197,
+                    Report::Corrupted('>') => 25137,
+                    _ => unreachable!(), // We kept only Corrupted variants
+                }
+            })
+            .sum();
+
+        Ok(corruption_score.into())
+    }
+
+    fn part2(lines: &Self::Input) -> PartResult {
+        let mut lines_completion_scores = lines
+            .iter()
+            .map(|line| analyze_line(line))
+            .filter_map(|rep| match rep {
+                Report::Incomplete { missing_chars } => {
+                    let completion_score: usize = missing_chars.iter().fold(0, |acc, chr| {
+                        acc * 5 + closing_char_to_completion_score(*chr)
+                    });
+                    Some((missing_chars, completion_score))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        lines_completion_scores.sort_by_key(|(_, score)| *score);
+
+        // for (missing_chars, compl_score) in &lines_completion_scores {
+        //     let chars_str = missing_chars.iter().collect::<String>();
+        //     println!(
+        //         "Missing chars '{}', completion score: {}",
+        //         chars_str, compl_score
+        //     );
+        // }
+
+        // Keep only the scores
+        let lines_completion_scores = lines_completion_scores
+            .iter()
+            .map(|(_, score)| score)
+            .collect::<Vec<_>>();
+
+        if lines_completion_scores.is_empty() {
+            bail!("No incomplete lines");
+        }
+        let mid_index = ((lines_completion_scores.len() - 1) as f64 / 2_f64).ceil() as usize;
+        Ok((*lines_completion_scores[mid_index]).into())
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT, 26397, 288957)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Answer;
+    use proptest::prelude::*;
+
     #[test]
     fn test_example_part1() {
-        let (result, _) = solve_part1(EXAMPLE_INPUT.trim());
-        assert_eq!(result, 26397);
+        let input = Day10::parse(EXAMPLE_INPUT).unwrap();
+        let result = Day10::part1(&input).unwrap();
+        assert_eq!(result, Answer::Int(26397));
     }
 
     #[test]
     fn test_example_part2() {
-        let (result, _) = solve_part2(EXAMPLE_INPUT.trim());
-        assert_eq!(result, 288957);
+        let input = Day10::parse(EXAMPLE_INPUT).unwrap();
+        let result = Day10::part2(&input).unwrap();
+        assert_eq!(result, Answer::Int(288957));
+    }
+
+    /// A random line of brackets, mostly well balanced, but some closing brackets are random.
+    fn bracket_line_strategy() -> impl Strategy<Value = String> {
+        prop::collection::vec(0..12_usize, 1..24).prop_map(|ops| {
+            let mut line = String::new();
+            let mut opener_stack = vec![];
+            for op in ops {
+                match (op / 4, opener_stack.pop()) {
+                    (1, Some(opener)) => line.push(Symbol::matching_pair(&opener)),
+                    (2, _) => line.push(Symbol::CLOSES[op % 4]),
+                    (_, last_opener) => {
+                        opener_stack.extend(last_opener);
+                        opener_stack.push(Symbol::OPENS[op % 4]);
+                        line.push(Symbol::OPENS[op % 4]);
+                    }
+                }
+            }
+            line
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parse_and_solve(lines in prop::collection::vec(bracket_line_strategy(), 1..30)) {
+            let input = Day10::parse(&lines.join("\n")).unwrap();
+            let parsed_lines: Vec<String> = (input.iter())
+                .map(|line| line.iter().map(Symbol::to_char).collect())
+                .collect();
+            prop_assert_eq!(&parsed_lines, &lines);
+            Day10::part1(&input).unwrap();
+            // NOTE: Without incomplete lines, there is no middle score and it gives an error.
+            let has_incomplete_lines = (input.iter())
+                .any(|line| matches!(analyze_line(line), Report::Incomplete { .. }));
+            prop_assert_eq!(Day10::part2(&input).is_ok(), has_incomplete_lines);
+        }
     }
 }
@@ -1,45 +1,75 @@
 // Day not_a_day
 
+use anyhow::Result as AnyResult;
 use chumsky as c;
 use chumsky::prelude::*;
 
+use crate::solution::{Example, Solution};
+use crate::{diagnostic, parsing, PartResult};
+
 // Format:
 //   foo
 //   barbar
 //   ...
-fn input_parser() -> impl Parser<char, Vec<String>, Error = Simple<char>> {
-    c::text::ident()
-        .separated_by(c::text::newline())
+pub fn input_parser() -> impl Parser<char, Vec<String>, Error = Simple<char>> {
+    parsing::lines(c::text::ident())
 }
 
-pub fn solve_part1(raw_input: &str) -> (usize, Option<usize>) {
-    let lines = input_parser().parse(raw_input).unwrap();
+// TODO: Replace by the example of the puzzle.
+static EXAMPLE_INPUT: &str = r#"
+foo
+barbar
+"#;
 
-    (0, None)
-}
+pub struct DayNN;
+
+impl Solution for DayNN {
+    type Input = Vec<String>;
+    const NAME: &'static str = "dayNN";
+    const TITLE: &'static str = "TODO";
+
+    fn parse(raw_input: &str) -> AnyResult<Self::Input> {
+        diagnostic::parse(input_parser(), raw_input)
+    }
 
-pub fn solve_part2(raw_input: &str) -> (usize, Option<usize>) {
-    let lines = input_parser().parse(raw_input).unwrap();
+    fn part1(lines: &Self::Input) -> PartResult {
+        Ok(lines.len().into())
+    }
+
+    fn part2(lines: &Self::Input) -> PartResult {
+        Ok(lines.len().into())
+    }
 
-    (0, None)
+    fn examples() -> Vec<Example> {
+        // TODO: Set the answers of the example once known, like:
+        //   vec![Example::new(EXAMPLE_INPUT, 42, 42)]
+        // NOTE: Without answers, the parts are not run on the example.
+        vec![Example {
+            input: EXAMPLE_INPUT,
+            part1: None,
+            part2: None,
+        }]
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    static EXAMPLE_INPUT: &str = r#"
-"#;
+    use crate::Answer;
 
     #[test]
+    #[ignore = "the example of the puzzle is not added yet"]
     fn test_example_part1() {
-        let (result, _) = solve_part1(EXAMPLE_INPUT.trim());
-        assert_eq!(result, 42);
+        let input = DayNN::parse(EXAMPLE_INPUT).unwrap();
+        let result = DayNN::part1(&input).unwrap();
+        assert_eq!(result, Answer::Int(42));
     }
 
     #[test]
+    #[ignore = "the example of the puzzle is not added yet"]
     fn test_example_part2() {
-        let (result, _) = solve_part2(EXAMPLE_INPUT.trim());
-        assert_eq!(result, 42);
+        let input = DayNN::parse(EXAMPLE_INPUT).unwrap();
+        let result = DayNN::part2(&input).unwrap();
+        assert_eq!(result, Answer::Int(42));
     }
 }
@@ -0,0 +1,186 @@
+// Human-readable reports of parsing errors
+
+use std::fmt;
+
+use anyhow::Result as AnyResult;
+use chumsky::error::{Simple, SimpleReason};
+use chumsky::Parser;
+
+use crate::input::{normalize, Normalized};
+
+/// Error of an input that cannot be parsed, with a report of all parsing errors.
+#[derive(Debug)]
+pub struct ParseError {
+    report: String,
+}
+
+impl ParseError {
+    /// Report of the errors of the normalized input, at their position in the raw input.
+    pub fn new(raw_input: &str, input: &Normalized, errors: &[Simple<char>]) -> Self {
+        let reports: Vec<_> = errors
+            .iter()
+            .map(|err| render_error(raw_input, input, err))
+            .collect();
+        Self {
+            report: reports.join("\n"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.report)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses the whole input with the given parser, errors are turned into a `ParseError`.
+///
+/// The input is normalized first (see `input::normalize`), so the parsers don't have to care
+/// about line endings or trailing whitespace. The errors are still reported at their line &
+/// column in the raw input, the one opened by the user.
+///
+/// NOTE: A parser stops silently at the first thing it can't parse, so the end of the input
+///       (after an optional final newline) is required, otherwise a malformed line would
+///       silently discard the rest of the input.
+pub fn parse<O>(
+    parser: impl Parser<char, O, Error = Simple<char>>,
+    raw_input: &str,
+) -> AnyResult<O> {
+    let input = normalize(raw_input, false);
+    parser
+        .then_ignore(chumsky::text::newline().or_not())
+        .then_ignore(chumsky::prelude::end())
+        .parse(input.text.as_str())
+        .map_err(|errs| ParseError::new(raw_input, &input, &errs).into())
+}
+
+fn fmt_token(token: &Option<char>) -> String {
+    match token {
+        // NOTE: Debug repr of a char escapes special chars like '\n', which would break the
+        //       report otherwise.
+        Some(chr) => format!("{:?}", chr),
+        None => "end of input".to_string(),
+    }
+}
+
+/// Finds the line & column (both starting at 1) of the char at the given index.
+fn line_col_at(text: &str, char_idx: usize) -> (usize, usize) {
+    let mut line = 1;
+    let mut col = 1;
+    for chr in text.chars().take(char_idx) {
+        if chr == '\n' {
+            line += 1;
+            col = 1;
+        } else {
+            col += 1;
+        }
+    }
+    (line, col)
+}
+
+// Renders the error like:
+//   error: found 'x' but expected one of '0', '1'
+//    --> line 3, column 5
+//     |
+//   3 | 12x4
+//     |   ^
+fn render_error(raw_input: &str, input: &Normalized, err: &Simple<char>) -> String {
+    let message = match err.reason() {
+        SimpleReason::Custom(msg) => msg.clone(),
+        SimpleReason::Unclosed { delimiter, .. } => format!("unclosed delimiter {:?}", delimiter),
+        SimpleReason::Unexpected => {
+            let found = fmt_token(&err.found().cloned());
+            let mut expected: Vec<_> = err.expected().map(fmt_token).collect();
+            // The expected tokens are stored in a HashSet, sort them to have a stable report.
+            expected.sort();
+            match expected.len() {
+                0 => format!("found unexpected {}", found),
+                1 => format!("found {} but expected {}", found, expected[0]),
+                _ => format!(
+                    "found {} but expected one of {}",
+                    found,
+                    expected.join(", ")
+                ),
+            }
+        }
+    };
+    let message = match err.label() {
+        Some(label) => format!("{} (while parsing {})", message, label),
+        None => message,
+    };
+
+    let span = err.span();
+    // NOTE: The lines of the normalized input are the raw ones without the first blank lines and
+    //       without their (removed) indentation, the rest of a line is left as is.
+    let (line, col) = line_col_at(&input.text, span.start);
+    let (line, col) = (line + input.skipped_lines, col + input.indent);
+    let line_content = raw_input.lines().nth(line - 1).unwrap_or("");
+    let line_num = line.to_string();
+    let margin = " ".repeat(line_num.len());
+    let marker_len = span.end.saturating_sub(span.start).max(1);
+
+    let mut report = format!("error: {}\n", message);
+    report += &format!("{}--> line {}, column {}\n", margin, line, col);
+    report += &format!("{} |\n", margin);
+    report += &format!("{} | {}\n", line_num, line_content);
+    report += &format!(
+        "{} | {}{}",
+        margin,
+        " ".repeat(col - 1),
+        "^".repeat(marker_len)
+    );
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chumsky::prelude::*;
+
+    fn ab_lines_parser() -> impl Parser<char, Vec<Vec<char>>, Error = Simple<char>> {
+        let ab_line = just('a').or(just('b')).repeated().at_least(1);
+        // NOTE: The input is normalized, it always ends with a newline.
+        (ab_line.separated_by(just('\n')))
+            .then_ignore(just('\n').or_not())
+            .then_ignore(end())
+    }
+
+    #[test]
+    fn test_line_col_at() {
+        let input = "ab\ncd\nef";
+        assert_eq!(line_col_at(input, 0), (1, 1));
+        assert_eq!(line_col_at(input, 1), (1, 2));
+        assert_eq!(line_col_at(input, 3), (2, 1));
+        assert_eq!(line_col_at(input, 7), (3, 2));
+    }
+
+    #[test]
+    fn test_parse_ok() {
+        let lines = parse(ab_lines_parser(), "ab\nba").unwrap();
+        assert_eq!(lines, vec![vec!['a', 'b'], vec!['b', 'a']]);
+    }
+
+    #[test]
+    fn test_parse_error_in_raw_input() {
+        let err = parse(ab_lines_parser(), "\n\nab\nbx\n").unwrap_err();
+        let report = err.to_string();
+        assert!(report.contains(" --> line 4, column 2\n"), "{}", report);
+        assert!(report.contains("4 | bx\n"), "{}", report);
+    }
+
+    #[test]
+    fn test_parse_error_report() {
+        let err = parse(ab_lines_parser(), "ab\nbax").unwrap_err();
+        let report = err.downcast::<ParseError>().unwrap().to_string();
+        let expected_report = [
+            "error: found 'x' but expected one of '\\n', 'a', 'b', end of input",
+            " --> line 2, column 3",
+            "  |",
+            "2 | bax",
+            "  |   ^",
+        ];
+        assert_eq!(report, expected_report.join("\n"));
+    }
+}
@@ -0,0 +1,209 @@
+// Download of the puzzle inputs from the website
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result as AnyResult};
+
+use crate::scaffold::day_number;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2021;
+pub const SESSION_ENV_VAR: &str = "AOC_SESSION";
+pub const DEFAULT_SESSION_FILE: &str = "./.aoc/session";
+
+/// Makes the HTTP requests to the website.
+///
+/// NOTE: A trait, so that another HTTP client can be used (or a fake one).
+pub trait HttpBackend {
+    /// Gets the body of the page at `url`, authenticated with the `session` cookie.
+    fn get(&self, url: &str, session: &str) -> AnyResult<String>;
+    /// Posts the `form` to `url`, authenticated with the `session` cookie, gives the body of the
+    /// response.
+    fn post_form(&self, url: &str, session: &str, form: &[(&str, &str)]) -> AnyResult<String>;
+}
+
+/// The default backend, using `ureq`.
+pub struct UreqBackend;
+
+impl UreqBackend {
+    fn request(method: &str, url: &str, session: &str) -> ureq::Request {
+        ureq::request(method, url)
+            .set("Cookie", &format!("session={}", session))
+            // NOTE: The website asks automated tools to identify themselves.
+            .set("User-Agent", "github.com/bew/adventofcode-2021 runner")
+    }
+
+    fn body(url: &str, response: Result<ureq::Response, ureq::Error>) -> AnyResult<String> {
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(code, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                bail!("HTTP {} for {}: {}", code, url, body.trim())
+            }
+            Err(err) => Err(err).with_context(|| format!("Cannot request {}", url)),
+        }
+    }
+}
+
+impl HttpBackend for UreqBackend {
+    fn get(&self, url: &str, session: &str) -> AnyResult<String> {
+        Self::body(url, Self::request("GET", url, session).call())
+    }
+
+    fn post_form(&self, url: &str, session: &str, form: &[(&str, &str)]) -> AnyResult<String> {
+        Self::body(url, Self::request("POST", url, session).send_form(form))
+    }
+}
+
+/// Finds the session token, from the env var or else from the session file.
+pub fn session_token(session_file: &Path) -> AnyResult<String> {
+    if let Ok(token) = std::env::var(SESSION_ENV_VAR) {
+        if !token.trim().is_empty() {
+            return Ok(token.trim().to_string());
+        }
+    }
+    let token = std::fs::read_to_string(session_file).with_context(|| {
+        format!(
+            "No session token, set {} or write it in {}",
+            SESSION_ENV_VAR,
+            session_file.display()
+        )
+    })?;
+    Ok(token.trim().to_string())
+}
+
+/// Client for the website of a given year.
+pub struct AocClient<'a> {
+    backend: &'a dyn HttpBackend,
+    base_url: String,
+    session: String,
+}
+
+impl<'a> AocClient<'a> {
+    pub fn new(backend: &'a dyn HttpBackend, base_url: &str, session: String) -> Self {
+        Self {
+            backend,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+        }
+    }
+
+    pub fn day_url(&self, day_name: &str) -> AnyResult<String> {
+        let number = day_number(day_name)?;
+        Ok(format!("{}/{}/day/{}", self.base_url, YEAR, number))
+    }
+
+    pub fn get_input(&self, day_name: &str) -> AnyResult<String> {
+        let url = format!("{}/input", self.day_url(day_name)?);
+        self.backend.get(&url, &self.session)
+    }
+
+    /// Submits the answer of the part, gives the page of the website's response.
+    pub fn post_answer(&self, day_name: &str, part: u8, answer: &str) -> AnyResult<String> {
+        let url = format!("{}/answer", self.day_url(day_name)?);
+        let level = part.to_string();
+        let form = [("level", level.as_str()), ("answer", answer)];
+        self.backend.post_form(&url, &self.session, &form)
+    }
+}
+
+/// Whether the input was downloaded, or already there.
+#[derive(Debug, PartialEq, Eq)]
+pub enum FetchStatus {
+    Downloaded,
+    Cached,
+}
+
+/// Writes the input given by `download` to `input_path`, unless it already exists.
+///
+/// NOTE: An empty input (as created by the `new` command) is not a real input, it is replaced.
+pub fn fetch_input(
+    input_path: &Path,
+    download: impl FnOnce() -> AnyResult<String>,
+) -> AnyResult<FetchStatus> {
+    let is_cached = std::fs::metadata(input_path)
+        .map(|m| m.len() > 0)
+        .unwrap_or(false);
+    if is_cached {
+        return Ok(FetchStatus::Cached);
+    }
+    let input = download()?;
+    if let Some(dir) = input_path.parent() {
+        std::fs::create_dir_all(dir).with_context(|| format!("Cannot create {}", dir.display()))?;
+    }
+    std::fs::write(input_path, input)
+        .with_context(|| format!("Cannot write {}", input_path.display()))?;
+    Ok(FetchStatus::Downloaded)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    use super::*;
+
+    /// Serves a single request with the given status & body, and gives back the request's head.
+    fn stub_server(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, std::thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut head = String::new();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                head += &line;
+            }
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+            head
+        });
+        (base_url, handle)
+    }
+
+    #[test]
+    fn test_get_input_from_stub() {
+        let (base_url, server) = stub_server("200 OK", "1,2,3\n");
+        let client = AocClient::new(&UreqBackend, &base_url, "s3cr3t".to_string());
+        assert_eq!(client.get_input("day06").unwrap(), "1,2,3\n");
+        let head = server.join().unwrap();
+        assert!(head.starts_with("GET /2021/day/6/input "));
+        assert!(head.contains("session=s3cr3t"));
+
+        let (base_url, server) = stub_server("400 Bad Request", "Please log in");
+        let client = AocClient::new(&UreqBackend, &base_url, "bad".to_string());
+        let err = client.get_input("day06").unwrap_err().to_string();
+        assert!(err.starts_with("HTTP 400"), "{}", err);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_fetch_input_never_redownloads() {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-test-{}", std::process::id()));
+        let input_path = dir.join("inputs").join("day06.txt");
+        // NOTE: An empty input is downloaded again.
+        std::fs::create_dir_all(input_path.parent().unwrap()).unwrap();
+        std::fs::write(&input_path, "").unwrap();
+
+        let status = fetch_input(&input_path, || Ok("1,2,3\n".to_string())).unwrap();
+        assert_eq!(status, FetchStatus::Downloaded);
+        let status = fetch_input(&input_path, || panic!("must not download")).unwrap();
+        assert_eq!(status, FetchStatus::Cached);
+        assert_eq!(std::fs::read_to_string(&input_path).unwrap(), "1,2,3\n");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,335 @@
+// 2D grids (dense or sparse) and positions in them
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Add, Index, IndexMut};
+
+/// Position in a grid, `x` goes right and `y` goes down.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pos {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Pos {
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    /// The 4 positions next to this one: left, right, up & down.
+    pub fn neighbours4(self) -> impl Iterator<Item = Pos> {
+        [(-1, 0), (1, 0), (0, -1), (0, 1)]
+            .into_iter()
+            .map(move |(dx, dy)| self + Pos::new(dx, dy))
+    }
+
+    /// The 8 positions around this one, with the diagonals.
+    pub fn neighbours8(self) -> impl Iterator<Item = Pos> {
+        (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| Pos::new(dx, dy)))
+            .filter(|delta| *delta != Pos::new(0, 0))
+            .map(move |delta| self + delta)
+    }
+}
+
+impl Add for Pos {
+    type Output = Pos;
+
+    fn add(self, other: Pos) -> Pos {
+        Pos::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+// NOTE: A oneline Debug representation, even in pretty debug (`{:#?}`) of a big structure.
+impl fmt::Debug for Pos {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Pos({}, {})", self.x, self.y)
+    }
+}
+
+impl From<(i32, i32)> for Pos {
+    fn from((x, y): (i32, i32)) -> Self {
+        Pos::new(x, y)
+    }
+}
+
+/// Dense grid: all the positions from `(0, 0)` to `(width - 1, height - 1)` have a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// The cells, row after row.
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Makes a grid from its rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, String> {
+        let width = rows.first().map_or(0, |row| row.len());
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(format!(
+                    "Row {} has {} cells, but the first row has {}",
+                    y,
+                    row.len(),
+                    width
+                ));
+            }
+            cells.extend(row);
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.index_of(pos).is_some()
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        let (x, y) = (usize::try_from(pos.x).ok()?, usize::try_from(pos.y).ok()?);
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    /// Gives the value at the position, `None` when it is out of the grid.
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(|idx| &mut self.cells[idx])
+    }
+
+    fn pos_of(&self, idx: usize) -> Pos {
+        Pos::new((idx % self.width) as i32, (idx / self.width) as i32)
+    }
+
+    /// Iterates over all the cells, row after row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        (self.cells.iter().enumerate()).map(|(idx, value)| (self.pos_of(idx), value))
+    }
+
+    /// Iterates over the 4 neighbours of the position that are in the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        (pos.neighbours4()).filter_map(|pos| Some((pos, self.get(pos)?)))
+    }
+
+    /// Iterates over the 8 neighbours of the position (with the diagonals) that are in the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        (pos.neighbours8()).filter_map(|pos| Some((pos, self.get(pos)?)))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        (self.cells.iter()).skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|x| self.column(x))
+    }
+}
+
+impl Grid<char> {
+    /// Reads a block of characters, one row per line.
+    pub fn parse_chars(text: &str) -> Result<Self, String> {
+        Grid::from_rows(text.lines().map(|line| line.chars().collect()).collect())
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is out of the grid", pos))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is out of the grid", pos))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for value in row {
+                write!(f, "{}", value)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Sparse grid: only some positions have a value, and there are no bounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Pos, T>,
+}
+
+// NOTE: Not derived, to not require `T: Default`.
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    /// Sets the value at the position, gives the previous one.
+    pub fn insert(&mut self, pos: Pos, value: T) -> Option<T> {
+        self.cells.insert(pos, value)
+    }
+
+    /// Gives the value at the position, set to the default value if there was none.
+    pub fn get_or_default(&mut self, pos: Pos) -> &mut T
+    where
+        T: Default,
+    {
+        self.cells.entry(pos).or_default()
+    }
+
+    /// Iterates over the positions with a value (in no particular order).
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.cells.iter().map(|(pos, value)| (*pos, value))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> + '_ {
+        self.cells.values()
+    }
+
+    /// Iterates over the 4 neighbours of the position that have a value.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        (pos.neighbours4()).filter_map(|pos| Some((pos, self.get(pos)?)))
+    }
+
+    /// Iterates over the 8 neighbours of the position (with the diagonals) that have a value.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        (pos.neighbours8()).filter_map(|pos| Some((pos, self.get(pos)?)))
+    }
+
+    /// Gives the smallest & the biggest corners of the area with values, `None` if there are no
+    /// values.
+    pub fn bounds(&self) -> Option<(Pos, Pos)> {
+        let mut positions = self.cells.keys();
+        let first = *positions.next()?;
+        Some(positions.fold((first, first), |(min, max), pos| {
+            (
+                Pos::new(min.x.min(pos.x), min.y.min(pos.y)),
+                Pos::new(max.x.max(pos.x), max.y.max(pos.y)),
+            )
+        }))
+    }
+}
+
+/// Displays the area with values, a position without a value is displayed as a `.`.
+impl<T: fmt::Display> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((min, max)) = self.bounds() else {
+            return Ok(());
+        };
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                match self.get(Pos::new(x, y)) {
+                    Some(value) => write!(f, "{}", value)?,
+                    None => write!(f, ".")?,
+                };
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid() {
+        let grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Pos::new(2, 1)], 6);
+        assert_eq!(grid.get(Pos::new(3, 0)), None);
+        assert_eq!(grid.get(Pos::new(-1, 0)), None);
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(1).collect::<Vec<_>>(), vec![&2, &5]);
+        assert_eq!(grid.to_string(), "123\n456\n");
+
+        // Only the neighbours in the grid
+        let neighbours: Vec<_> = grid.neighbours4(Pos::new(0, 0)).map(|(_, v)| *v).collect();
+        assert_eq!(neighbours, vec![2, 4]);
+        assert_eq!(grid.neighbours8(Pos::new(1, 0)).count(), 5);
+
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_err());
+        assert_eq!(Grid::parse_chars("#.\n.#").unwrap()[Pos::new(1, 1)], '#');
+    }
+
+    #[test]
+    fn test_sparse_grid() {
+        let mut grid = SparseGrid::new();
+        *grid.get_or_default(Pos::new(1, 2)) += 1;
+        *grid.get_or_default(Pos::new(1, 2)) += 1;
+        grid.insert(Pos::new(3, 1), 1);
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.get(Pos::new(1, 2)), Some(&2));
+        assert_eq!(grid.bounds(), Some((Pos::new(1, 1), Pos::new(3, 2))));
+        assert_eq!(grid.to_string(), "..1\n2..\n");
+        assert_eq!(grid.neighbours8(Pos::new(2, 1)).count(), 2);
+        assert_eq!(Pos::new(0, 0).neighbours8().count(), 8);
+    }
+}
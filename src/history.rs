@@ -0,0 +1,253 @@
+// History of the results of the runs, to see regressions of the answers or of the timings
+
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result as AnyResult};
+use serde::{Deserialize, Serialize};
+
+use crate::answers::input_hash;
+use crate::outcome::PartOutcome;
+use crate::report::PartRecord;
+use crate::time::now;
+use crate::Answer;
+
+pub const DEFAULT_HISTORY_PATH: &str = "./.aoc/history.jsonl";
+
+/// Result of a part in a run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Time of the run, in seconds since the Unix epoch.
+    pub time: u64,
+    /// Commit of the code that was run, like `77d3436` (or `77d3436-dirty` with local changes).
+    pub commit: String,
+    /// NOTE: A debug build is much slower than a release one, their timings can't be compared.
+    pub release: bool,
+    pub day: String,
+    pub part: u8,
+    /// Hash of the input, like in the answers file.
+    pub input: String,
+    pub status: String,
+    pub value: Option<Answer>,
+    pub duration_ms: f64,
+}
+
+impl HistoryEntry {
+    pub fn new(day_name: &str, part: u8, input: &str, outcome: &PartOutcome) -> Self {
+        let record = PartRecord::new(day_name, part, outcome);
+        Self {
+            time: now(),
+            commit: String::new(),
+            release: !cfg!(debug_assertions),
+            day: day_name.to_string(),
+            part,
+            input: input_hash(input),
+            status: record.status.to_string(),
+            value: record.value,
+            duration_ms: record.duration_ms,
+        }
+    }
+
+    /// Whether the two entries are about the same thing, and can be compared.
+    fn is_comparable(&self, other: &HistoryEntry) -> bool {
+        (&self.day, self.part, &self.input, self.release)
+            == (&other.day, other.part, &other.input, other.release)
+    }
+
+    /// Warnings about this entry compared to the previous one: a changed answer, or a slowdown
+    /// of more than `max_slowdown` percent.
+    pub fn regressions(&self, previous: &HistoryEntry, max_slowdown: f64) -> Vec<String> {
+        let mut warnings = vec![];
+        if self.value != previous.value {
+            let was = previous.value.as_ref().map(|v| v.to_string());
+            warnings.push(format!(
+                "answer changed (was {})",
+                was.unwrap_or_else(|| previous.status.clone())
+            ));
+        }
+        let slowdown = slowdown_percent(previous.duration_ms, self.duration_ms);
+        if slowdown > max_slowdown {
+            warnings.push(format!("{:.0}% slower", slowdown));
+        }
+        warnings
+    }
+}
+
+fn slowdown_percent(previous_ms: f64, current_ms: f64) -> f64 {
+    if previous_ms <= 0.0 {
+        return 0.0;
+    }
+    (current_ms - previous_ms) / previous_ms * 100.0
+}
+
+/// Gives the current commit, with `-dirty` when there are local changes.
+fn git_commit() -> String {
+    let git = |args: &[&str]| {
+        let output = Command::new("git").args(args).output().ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(commit) => match git(&["status", "--porcelain", "--untracked-files=no"]) {
+            Some(changes) if !changes.is_empty() => format!("{}-dirty", commit),
+            _ => commit,
+        },
+        None => "unknown".to_string(),
+    }
+}
+
+/// Appends the entries of a run to the history file, with the current commit.
+pub fn append_run(path: &Path, mut entries: Vec<HistoryEntry>) -> AnyResult<()> {
+    let commit = git_commit();
+    let mut lines = String::new();
+    for entry in &mut entries {
+        entry.commit = commit.clone();
+        lines += &serde_json::to_string(entry)?;
+        lines.push('\n');
+    }
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(lines.as_bytes()))
+        .with_context(|| format!("Cannot write history file {}", path.display()))
+}
+
+/// Loads all the entries of the history file, a missing file gives no entries.
+pub fn load(path: &Path) -> AnyResult<Vec<HistoryEntry>> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Cannot read history file {}", path.display()))?;
+    content
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Invalid line {} of {}", idx + 1, path.display()))
+        })
+        .collect()
+}
+
+/// Formats the time (in seconds since the Unix epoch) as a UTC date & time like
+/// `2021-12-01 05:00`.
+fn fmt_time(time: u64) -> String {
+    let (days, secs_of_day) = ((time / 86400) as i64, time % 86400);
+    // NOTE: Civil date from the number of days since 1970-01-01, from Howard Hinnant's
+    //       `civil_from_days` algorithm.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60
+    )
+}
+
+/// Displays the last `count` runs of the part, with the regressions of each run compared to the
+/// previous run on the same input.
+pub fn show_part(
+    entries: &[HistoryEntry],
+    day_name: &str,
+    part: u8,
+    count: usize,
+    max_slowdown: f64,
+) {
+    let part_entries: Vec<_> = entries
+        .iter()
+        .filter(|e| e.day == day_name && e.part == part)
+        .collect();
+    println!("=>> {} Part{} <<=", day_name, part);
+    if part_entries.is_empty() {
+        println!("No runs yet");
+        return;
+    }
+    let first_shown = part_entries.len().saturating_sub(count);
+    for (idx, entry) in part_entries.iter().enumerate().skip(first_shown) {
+        let previous = part_entries[..idx]
+            .iter()
+            .rev()
+            .find(|prev| prev.is_comparable(entry));
+        let mut line = format!(
+            "{}  {:<15} {:<8} {:>15} {:>10.3}ms",
+            fmt_time(entry.time),
+            entry.commit,
+            if entry.release { "release" } else { "debug" },
+            entry
+                .value
+                .as_ref()
+                .map_or(entry.status.clone(), |v| v.to_string()),
+            entry.duration_ms,
+        );
+        if let Some(previous) = previous {
+            let slowdown = slowdown_percent(previous.duration_ms, entry.duration_ms);
+            line += &format!(" ({:+.0}%)", slowdown);
+            for warning in entry.regressions(previous, max_slowdown) {
+                line += &format!("  ⚠️ {}", warning);
+            }
+        }
+        println!("{}", line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(value: i64, duration_ms: f64) -> HistoryEntry {
+        HistoryEntry {
+            time: 0,
+            commit: "abc1234".to_string(),
+            release: true,
+            day: "day01".to_string(),
+            part: 1,
+            input: input_hash("1,2,3"),
+            status: "solved".to_string(),
+            value: Some(Answer::Int(value)),
+            duration_ms,
+        }
+    }
+
+    #[test]
+    fn test_regressions() {
+        let previous = entry(42, 10.0);
+        assert!(entry(42, 11.0).regressions(&previous, 20.0).is_empty());
+        assert_eq!(
+            entry(42, 15.0).regressions(&previous, 20.0),
+            vec!["50% slower".to_string()]
+        );
+        assert_eq!(
+            entry(41, 5.0).regressions(&previous, 20.0),
+            vec!["answer changed (was 42)".to_string()]
+        );
+        let debug_entry = HistoryEntry {
+            release: false,
+            ..entry(42, 100.0)
+        };
+        assert!(!debug_entry.is_comparable(&previous));
+    }
+
+    #[test]
+    fn test_fmt_time() {
+        assert_eq!(fmt_time(0), "1970-01-01 00:00");
+        assert_eq!(fmt_time(1_638_334_800), "2021-12-01 05:00");
+        assert_eq!(fmt_time(951_782_400), "2000-02-29 00:00");
+    }
+}
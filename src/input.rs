@@ -0,0 +1,154 @@
+// Where the puzzle input of a day comes from
+
+use std::io::Read;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    /// Read until the end of the standard input, to pipe generated inputs.
+    Stdin,
+    /// Given directly on the command line, for quick examples.
+    Inline(String),
+}
+
+impl InputSource {
+    /// Source for a path given on the command line, where `-` means stdin (as usual).
+    pub fn from_path(path: PathBuf) -> Self {
+        if path.as_os_str() == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(path)
+        }
+    }
+
+    /// Short description of the source, for messages & the answers store.
+    pub fn label(&self) -> String {
+        match self {
+            InputSource::File(path) => path.display().to_string(),
+            InputSource::Stdin => "<stdin>".to_string(),
+            InputSource::Inline(_) => "<inline>".to_string(),
+        }
+    }
+
+    /// Reads the input, the error is a message ready to be displayed in a part's outcome.
+    pub fn read(&self) -> Result<String, String> {
+        let result = match self {
+            InputSource::File(path) => std::fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut buf = String::new();
+                std::io::stdin().read_to_string(&mut buf).map(|_| buf)
+            }
+            InputSource::Inline(input) => Ok(dedent(input)),
+        };
+        result.map_err(|err| format!("Cannot read input {}: {}", self.label(), err))
+    }
+}
+
+/// Input once normalized, with what was removed at the start of its lines to find the
+/// positions in the raw input again (e.g. for parse errors).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Normalized {
+    pub text: String,
+    /// Number of blank lines removed before the first line.
+    pub skipped_lines: usize,
+    /// Indentation removed at the start of every line (only when dedented).
+    pub indent: usize,
+}
+
+/// Cleans a raw input before parsing it, to read downloaded files & files with Windows line
+/// endings the same way:
+/// - `\r\n` line endings become `\n`,
+/// - the trailing whitespace of the lines, and the blank lines at the start & at the end are
+///   removed,
+/// - with `dedent`, when all the lines are indented, their common indentation is removed (like
+///   `indoc!`, for examples written in the code or on the command line),
+/// - it ends with a single newline.
+pub fn normalize(raw_input: &str, dedent: bool) -> Normalized {
+    // NOTE: `lines()` already splits on `\r\n`, and a stray `\r` at the end of a line is
+    //       trailing whitespace.
+    let lines: Vec<&str> = raw_input.lines().map(str::trim_end).collect();
+    let Some(first) = lines.iter().position(|line| !line.is_empty()) else {
+        return Normalized {
+            text: String::new(),
+            skipped_lines: 0,
+            indent: 0,
+        };
+    };
+    let last = lines.iter().rposition(|line| !line.is_empty()).unwrap();
+    let lines = &lines[first..=last];
+
+    let indent_of = |line: &str| line.len() - line.trim_start_matches([' ', '\t']).len();
+    let indent = match dedent {
+        true => (lines.iter())
+            .filter(|line| !line.is_empty())
+            .map(|line| indent_of(line))
+            .min()
+            .unwrap_or(0),
+        false => 0,
+    };
+
+    let mut text = String::with_capacity(raw_input.len());
+    for line in lines {
+        // NOTE: Empty lines are shorter than the indentation.
+        text += line.get(indent..).unwrap_or("");
+        text.push('\n');
+    }
+    Normalized {
+        text,
+        skipped_lines: first,
+        indent,
+    }
+}
+
+/// Normalizes an input written in the code or on the command line, removing its indentation.
+pub fn dedent(raw_input: &str) -> String {
+    normalize(raw_input, true).text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(InputSource::from_path("-".into()), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_path("./in.txt".into()),
+            InputSource::File("./in.txt".into())
+        );
+    }
+
+    #[test]
+    fn test_read() {
+        let inline = InputSource::Inline("3,4,3,1,2".to_string());
+        assert_eq!(inline.read(), Ok("3,4,3,1,2\n".to_string()));
+
+        let missing = InputSource::File("./does/not/exist.txt".into());
+        let err = missing.read().unwrap_err();
+        assert!(err.starts_with("Cannot read input ./does/not/exist.txt: "));
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("1\r\n2  \r\n\r\n", false).text, "1\n2\n");
+        assert_eq!(normalize("1\n2", false).text, "1\n2\n");
+        assert_eq!(normalize("", false).text, "");
+        let example = "
+            22 13
+             8  2
+
+             3 15
+        ";
+        let expected = Normalized {
+            text: "22 13\n 8  2\n\n 3 15\n".to_string(),
+            skipped_lines: 1,
+            indent: 12,
+        };
+        assert_eq!(normalize(example, true), expected);
+        // Files are not dedented
+        assert_eq!(normalize("\n  1\n  2\n", false).text, "  1\n  2\n");
+        // Not all lines are indented, the indentation is kept
+        assert_eq!(dedent(" 8  2\n22 13\n"), " 8  2\n22 13\n");
+    }
+}
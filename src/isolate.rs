@@ -0,0 +1,266 @@
+// Solving parts in a child process, to stop them when they take too much time or memory
+
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result as AnyResult};
+
+use crate::outcome::{parse_failed, solve_parts, PartOutcome, PartStatus};
+use crate::{Answer, Day};
+
+/// Limits of a part, nothing is limited by default.
+#[derive(Debug, Default, Clone)]
+pub struct Limits {
+    pub timeout: Option<Duration>,
+    /// Max memory in bytes.
+    pub max_memory: Option<u64>,
+}
+
+impl Limits {
+    pub fn is_set(&self) -> bool {
+        self.timeout.is_some() || self.max_memory.is_some()
+    }
+}
+
+/// How a child process ended.
+#[derive(Debug)]
+enum ChildEnd {
+    Exited {
+        status: ExitStatus,
+        stdout: String,
+        stderr: String,
+    },
+    TimedOut,
+}
+
+/// Runs the command with the input on its stdin, and kills it when it takes more than `timeout`.
+fn run_child(mut command: Command, input: &str, timeout: Option<Duration>) -> AnyResult<ChildEnd> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Cannot start the child process")?;
+    // NOTE: The pipes are written & read while the child runs, otherwise a child writing more
+    //       than a pipe can hold (e.g. a long panic report) would wait forever for us to read
+    //       it, and would never end before the timeout.
+    let stdout = read_in_thread(child.stdout.take().unwrap());
+    let stderr = read_in_thread(child.stderr.take().unwrap());
+    let mut stdin = child.stdin.take().unwrap();
+    let input = input.to_string();
+    thread::spawn(move || {
+        // An error here means the child is already gone, its status tells why.
+        let _ = stdin.write_all(input.as_bytes());
+    });
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if timeout.is_some_and(|timeout| start.elapsed() > timeout) {
+            let _ = child.kill();
+            child.wait()?;
+            return Ok(ChildEnd::TimedOut);
+        }
+        thread::sleep(Duration::from_millis(5));
+    };
+    Ok(ChildEnd::Exited {
+        status,
+        stdout: join_output(stdout)?,
+        stderr: join_output(stderr)?,
+    })
+}
+
+fn read_in_thread(mut pipe: impl Read + Send + 'static) -> JoinHandle<std::io::Result<String>> {
+    thread::spawn(move || {
+        let mut output = String::new();
+        pipe.read_to_string(&mut output).map(|_| output)
+    })
+}
+
+fn join_output(reader: JoinHandle<std::io::Result<String>>) -> AnyResult<String> {
+    let output = reader.join().expect("Reading a pipe cannot panic");
+    output.context("Cannot read the output of the child process")
+}
+
+/// Whether the process was stopped like when it needs more memory than its limit: Rust aborts
+/// the process when an allocation fails (SIGABRT), and the OOM killer kills it (SIGKILL).
+#[cfg(unix)]
+fn stopped_by_memory(status: &ExitStatus) -> bool {
+    use std::os::unix::process::ExitStatusExt;
+    matches!(status.signal(), Some(libc::SIGABRT | libc::SIGKILL))
+}
+
+#[cfg(not(unix))]
+fn stopped_by_memory(_status: &ExitStatus) -> bool {
+    false
+}
+
+/// Gives the outcome of a part from how its child process ended.
+fn child_outcome(end: ChildEnd, limits: &Limits) -> PartOutcome {
+    let (status, stdout, stderr) = match end {
+        ChildEnd::TimedOut => {
+            return PartOutcome {
+                status: PartStatus::TimedOut,
+                duration: limits.timeout.unwrap_or_default(),
+            }
+        }
+        ChildEnd::Exited {
+            status,
+            stdout,
+            stderr,
+        } => (status, stdout, stderr),
+    };
+    if status.success() {
+        if let Ok(outcome) = serde_json::from_str::<PartOutcome>(&stdout) {
+            // NOTE: The process is only checked every few milliseconds, a part can end in time for
+            //       us but still be too slow.
+            if limits
+                .timeout
+                .is_some_and(|timeout| outcome.duration > timeout)
+            {
+                return PartOutcome {
+                    status: PartStatus::TimedOut,
+                    duration: outcome.duration,
+                };
+            }
+            return outcome;
+        }
+    }
+    // NOTE: The memory is only limited with `max_memory`, otherwise it is a real crash.
+    if limits.max_memory.is_some() && stopped_by_memory(&status) {
+        return PartOutcome {
+            status: PartStatus::OutOfMemory,
+            duration: Duration::ZERO,
+        };
+    }
+    PartOutcome::failed(format!(
+        "The child process failed ({}):\n{}",
+        status,
+        stderr.trim()
+    ))
+}
+
+/// Solves the parts one by one, each in a child process running the `solve-part` command of the
+/// runner at `exe`, within the limits.
+///
+/// NOTE: Each child parses the input again, the parsed input cannot be shared between processes.
+///       Once a child cannot parse the input, the other parts are not run, they fail the same way
+///       (see `outcome::parse_failed`).
+pub fn solve_parts_isolated(
+    exe: &Path,
+    day: &dyn Day,
+    raw_input: &str,
+    parts: &[(u8, Option<Answer>)],
+    limits: &Limits,
+) -> Vec<PartOutcome> {
+    let mut outcomes: Vec<PartOutcome> = vec![];
+    for (idx, (part, expected)) in parts.iter().enumerate() {
+        let mut command = Command::new(exe);
+        command.args(["solve-part", day.name(), "--part", &part.to_string()]);
+        if let Some(max_memory) = limits.max_memory {
+            command.args(["--max-memory", &max_memory.to_string()]);
+        }
+        let mut outcome = match run_child(command, raw_input, limits.timeout) {
+            Ok(end) => child_outcome(end, limits),
+            Err(err) => PartOutcome::failed(format!("{:#}", err)),
+        };
+        if let PartStatus::ParseError(_) = outcome.status {
+            let parts: Vec<_> = parts[idx..].iter().map(|(part, _)| *part).collect();
+            outcomes.extend(parse_failed(outcome.status, outcome.duration, &parts));
+            break;
+        }
+        outcome.status = outcome.status.with_expected(expected.clone());
+        outcomes.push(outcome);
+    }
+    outcomes
+}
+
+/// Limits the memory of the current process, in bytes.
+///
+/// NOTE: This limits the address space, which is a bit more than the memory really used (e.g.
+///       the program itself is in it).
+#[cfg(unix)]
+pub fn limit_memory(max_memory: u64) -> AnyResult<()> {
+    let limit = libc::rlimit {
+        rlim_cur: max_memory as libc::rlim_t,
+        rlim_max: max_memory as libc::rlim_t,
+    };
+    // SAFETY: setrlimit only reads the given struct.
+    if unsafe { libc::setrlimit(libc::RLIMIT_AS, &limit) } != 0 {
+        bail!(
+            "Cannot limit the memory: {}",
+            std::io::Error::last_os_error()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn limit_memory(_max_memory: u64) -> AnyResult<()> {
+    bail!("Limiting the memory is only supported on unix")
+}
+
+/// Body of the `solve-part` command: solves the part with the input read from stdin, and writes
+/// its outcome as JSON.
+pub fn solve_part_child(day: &dyn Day, part: u8, max_memory: Option<u64>) -> AnyResult<()> {
+    if let Some(max_memory) = max_memory {
+        limit_memory(max_memory)?;
+    }
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input)?;
+    let outcome = solve_parts(day, &input, &[(part, None)]).remove(0);
+    println!("{}", serde_json::to_string(&outcome)?);
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn shell(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        command
+    }
+
+    #[test]
+    fn test_run_child() {
+        let limits = Limits {
+            timeout: Some(Duration::from_millis(100)),
+            max_memory: Some(1 << 30),
+        };
+        let end = run_child(shell("sleep 5"), "", limits.timeout).unwrap();
+        let outcome = child_outcome(end, &limits);
+        assert_eq!(outcome.status, PartStatus::TimedOut);
+
+        let end = run_child(shell("kill -ABRT $$"), "", limits.timeout).unwrap();
+        assert_eq!(child_outcome(end, &limits).status, PartStatus::OutOfMemory);
+        let end = run_child(shell("kill -ABRT $$"), "", None).unwrap();
+        let outcome = child_outcome(end, &Limits::default());
+        assert_eq!(outcome.status.name(), "failed");
+
+        // Much more output than a pipe can hold, the child is not blocked
+        let script = "head -c 1000000 /dev/zero; head -c 1000000 /dev/zero >&2";
+        let end = run_child(shell(script), "", Some(Duration::from_secs(5))).unwrap();
+        assert!(
+            matches!(&end, ChildEnd::Exited { stdout, stderr, .. } if stdout.len() == 1_000_000 && stderr.len() == 1_000_000),
+            "{:?}",
+            end
+        );
+
+        let json = r#"{"status":{"Unknown":42},"duration":{"secs":0,"nanos":1000}}"#;
+        let end = run_child(
+            shell(&format!("cat >/dev/null; echo '{}'", json)),
+            "1,2",
+            None,
+        );
+        let outcome = child_outcome(end.unwrap(), &limits);
+        let status = outcome.status.with_expected(Some(Answer::Int(42)));
+        assert_eq!(status, PartStatus::Solved(Answer::Int(42)));
+    }
+}
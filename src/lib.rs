@@ -0,0 +1,36 @@
+// Library of the solutions, with the registry of all days
+
+use anyhow::Result as AnyResult;
+
+pub use answer::Answer;
+pub use solution::{Day, Example, Solution};
+
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod cross;
+pub mod diagnostic;
+pub mod fetch;
+pub mod grid;
+pub mod history;
+pub mod input;
+pub mod isolate;
+pub mod outcome;
+pub mod panics;
+pub mod parallel;
+pub mod parsing;
+pub mod report;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+pub mod time;
+pub mod watch;
+
+/// Result of a part's solver: the answer, or the error that prevented to solve it.
+pub type PartResult = AnyResult<Answer>;
+
+// NOTE: The days' modules & the `DAYS` registry are generated by the build script, from the
+//       `src/dayNN.rs` files: adding a day is only adding its file.
+//       The days have different input types, so they are stored as trait objects, which will use
+//       dynamic dispatch on use.
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
@@ -1,155 +1,661 @@
-use std::env;
 use std::path::{Path, PathBuf};
 use std::process::exit;
+use std::time::Duration;
 
-use anyhow::Result as AnyResult;
-
-// These lines DECLARE the modules of my app
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-
-// NOTE: We can't pass generic type that impl Read, so we pass a trait object, which will use
-//       dynamic dispatch on use.
-type PartFn = fn(&str) -> (usize, Option<usize>);
-
-// We define a lifetime in 'Day', to be able to store references in the struct.
-struct Day {
-    name: &'static str,
-    // description: &'static str,
-    part1: PartFn,
-    part2: PartFn,
-    default_input: &'static str,
+use anyhow::{bail, Result as AnyResult};
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::{Parser, Subcommand};
+
+use adventofcode_2021::answers::{AnswersStore, DEFAULT_ANSWERS_PATH};
+use adventofcode_2021::bench::{self, BenchConfig};
+use adventofcode_2021::cross::{self, Rng};
+use adventofcode_2021::fetch::{self, AocClient, FetchStatus, UreqBackend};
+use adventofcode_2021::history::{self, HistoryEntry, DEFAULT_HISTORY_PATH};
+use adventofcode_2021::input::{self, InputSource};
+use adventofcode_2021::isolate::{self, Limits};
+use adventofcode_2021::outcome::{solve_parts, solve_parts_with_jobs};
+use adventofcode_2021::outcome::{PartOutcome, PartStatus};
+use adventofcode_2021::report::{OutputFormat, Reporter};
+use adventofcode_2021::submit;
+use adventofcode_2021::{parallel, scaffold, time, watch, Answer, Day, DAYS};
+
+/// Parses a day name given on the command line to the matching day in `DAYS`.
+///
+/// NOTE: Using a `PossibleValuesParser` makes clap list the available days in `--help` and in
+///       the error message when an unknown day is given.
+fn day_parser() -> impl TypedValueParser<Value = &'static dyn Day> {
+    PossibleValuesParser::new(DAYS.iter().map(|day| day.name())).map(|name| {
+        // The possible values come from DAYS, so the day is always found.
+        *DAYS.iter().find(|day| day.name() == name).unwrap()
+    })
+}
+
+fn days_help() -> String {
+    let day_names: Vec<_> = DAYS.iter().map(|d| d.name()).collect();
+    format!("Available days: {}", day_names.join(", "))
+}
+
+/// Advent of Code 2021 solutions runner
+#[derive(Parser)]
+#[command(version, after_help = days_help())]
+struct Cli {
+    /// Only display the parts that are not solved as expected
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// Record the answers of the parts without a known answer (once you know they are right!)
+    #[arg(long, global = true)]
+    record: bool,
+
+    /// Format of the results of run, all, last & check commands
+    #[arg(short, long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    /// File where the known answers are stored
+    #[arg(long, global = true, default_value = DEFAULT_ANSWERS_PATH)]
+    answers: PathBuf,
+
+    /// File where the results of the runs are added, to see regressions with `history`
+    #[arg(long, global = true, default_value = DEFAULT_HISTORY_PATH)]
+    history: PathBuf,
+
+    /// Do not add the results of this run to the history
+    #[arg(long, global = true)]
+    no_history: bool,
+
+    /// Stop a part after this time (in seconds), each part is then solved in a child process
+    #[arg(long, global = true)]
+    timeout: Option<f64>,
+
+    /// Stop a part when it needs more memory (in MiB), each part is then solved in a child process
+    #[arg(long, global = true)]
+    max_memory: Option<u64>,
+
+    #[command(subcommand)]
+    cmd: Cmd,
+}
+
+#[derive(Subcommand)]
+enum Cmd {
+    /// Run a specific day
+    Run {
+        /// Day to run
+        #[arg(value_parser = day_parser())]
+        day: &'static dyn Day,
+        #[command(flatten)]
+        sel: PartSelection,
+        #[command(flatten)]
+        input: InputSelection,
+        /// Run the examples of the puzzle, instead of the day's input
+        #[arg(long, conflicts_with_all = ["input", "input_str"])]
+        example: bool,
+    },
+    /// Run all available days
+    All {
+        /// Number of days to solve concurrently (0 to use all CPUs)
+        #[arg(short, long, default_value_t = 1)]
+        jobs: usize,
+    },
+    /// Run the last available day (used while dev)
+    Last {
+        #[command(flatten)]
+        sel: PartSelection,
+    },
+    /// List available days
+    List,
+    /// Run days many times and display stats on the time taken to parse and solve each part
+    Bench {
+        /// Day to bench (all days if not given)
+        #[arg(value_parser = day_parser())]
+        day: Option<&'static dyn Day>,
+        /// Number of measured runs
+        #[arg(short = 'n', long, default_value_t = 10)]
+        runs: usize,
+        /// Number of runs before the measured ones
+        #[arg(short, long, default_value_t = 2)]
+        warmup: usize,
+        #[command(flatten)]
+        input: InputSelection,
+    },
+    /// Check that all days give the expected answers (exits with failure otherwise)
+    Check {
+        /// Also check the examples of each day, before its input
+        #[arg(long)]
+        examples: bool,
+        /// Also cross-check the parts with their reference implementations, on generated inputs
+        #[arg(long)]
+        cross: bool,
+    },
+    /// Create a new day from the template, and register it
+    New {
+        /// Name of the day, like day11
+        name: String,
+        /// Title of the day's puzzle
+        #[arg(short, long)]
+        title: String,
+    },
+    /// Download the input of a day (never downloads it again once it exists)
+    Fetch {
+        /// Name of the day, like day11
+        name: String,
+        #[command(flatten)]
+        website: WebsiteArgs,
+    },
+    /// Submit the answer of a part to the website, and record the verdict in the answers
+    Submit {
+        /// Day to submit
+        #[arg(value_parser = day_parser())]
+        day: &'static dyn Day,
+        /// Part to submit
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        #[command(flatten)]
+        website: WebsiteArgs,
+    },
+    /// Show the results of the last runs of a day, with the changed answers & slowdowns
+    History {
+        /// Day to show
+        #[arg(value_parser = day_parser())]
+        day: &'static dyn Day,
+        #[command(flatten)]
+        sel: PartSelection,
+        /// Number of runs to show
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: usize,
+        /// Slowdown (in percent) compared to the previous run, above which a run is flagged
+        #[arg(long, default_value_t = 20.0)]
+        slowdown: f64,
+    },
+    /// Solve a part with the input read from stdin, and write its outcome as JSON (used to solve
+    /// parts in a child process)
+    #[command(hide = true)]
+    SolvePart {
+        #[arg(value_parser = day_parser())]
+        day: &'static dyn Day,
+        #[arg(long)]
+        part: u8,
+        /// Max memory in bytes
+        #[arg(long)]
+        max_memory: Option<u64>,
+    },
+    /// Re-run a day's example tests & real input whenever its source or input changes
+    Watch {
+        /// Day to watch
+        #[arg(value_parser = day_parser())]
+        day: &'static dyn Day,
+        /// Time between two checks for changes, in milliseconds
+        #[arg(long, default_value_t = 500)]
+        interval: u64,
+    },
+}
+
+#[derive(clap::Args)]
+struct WebsiteArgs {
+    /// Base URL of the website (e.g. a local server when testing)
+    #[arg(long, env = "AOC_BASE_URL", default_value = fetch::DEFAULT_BASE_URL)]
+    base_url: String,
+    /// File with the session token, when the AOC_SESSION env var is not set
+    #[arg(long, default_value = fetch::DEFAULT_SESSION_FILE)]
+    session_file: PathBuf,
+}
+
+impl WebsiteArgs {
+    fn client(&self) -> AnyResult<AocClient<'static>> {
+        let session = fetch::session_token(&self.session_file)?;
+        Ok(AocClient::new(&UreqBackend, &self.base_url, session))
+    }
+}
+
+#[derive(clap::Args)]
+struct PartSelection {
+    /// Only run the given part
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
 }
 
-macro_rules! def_day {
-    // ($d: ident, $desc: tt) => {
-    ($d: ident) => {
-        Day {
-            name: stringify!($d),
-            // description: stringify!($desc),
-            part1: $d::solve_part1,
-            part2: $d::solve_part2,
-            default_input: concat!("./inputs/", stringify!($d), ".txt"),
+impl PartSelection {
+    fn all() -> Self {
+        Self { part: None }
+    }
+
+    fn parts(&self) -> Vec<u8> {
+        match self.part {
+            Some(n) => vec![n],
+            None => vec![1, 2],
         }
-    };
+    }
+}
+
+#[derive(clap::Args)]
+struct InputSelection {
+    /// Custom input file to use instead of the day's default input (`-` for stdin)
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+    /// Input given directly, instead of the day's default input (e.g. an example)
+    #[arg(long, conflicts_with = "input")]
+    input_str: Option<String>,
 }
 
-static DAYS: &[Day] = &[
-    // Day {
-    //     name: "day01",
-    //     part1: day01::solve_part1,
-    //     part2: day01::solve_part2,
-    //     default_input: "./inputs/day01.txt",
-    // },
-    // same as:
-    def_day!(day01), // Sonar Sweep
-    // ---
-    def_day!(day02), // Dive!
-    def_day!(day03), // Binary Diagnostic
-    def_day!(day04), // Giant Squid (bingo simulation)
-    def_day!(day05), // Hydrothermal Venture (crossing lines)
-    def_day!(day06), // Lanternfish (recursive fish colony)
-    def_day!(day07), // The Treachery of Whales (efficient crab movements)
-    def_day!(day08), // Seven Segment Search
-    def_day!(day09), // Smoke Basin (find low points & basins in a heightmap) FOR NOW...
-    def_day!(day10), // Syntax Scoring ([}(])>>)
-];
-
-fn print_usage() {
-    let prog_name = env::args().next().unwrap_or("prog".to_string());
-    let day_names: Vec<_> = DAYS.iter().map(|d| d.name).collect();
-    println!("Usage:");
-    println!("  {} <cmd>", prog_name);
-    println!("  {} <day> [<custom_input_path>]", prog_name);
-    println!("");
-
-    println!("<cmd> can be:");
-    println!("  all   - run all available days");
-    println!("  last  - run the last available day (used while dev)");
-    println!("  list  - list available days");
-    println!("");
-
-    let joined_days = day_names.join(", ");
-    println!("<day> can be one of: {}", joined_days);
-    println!("");
-    exit(1);
+impl InputSelection {
+    fn is_set(&self) -> bool {
+        self.input.is_some() || self.input_str.is_some()
+    }
+
+    fn source(self, day: &dyn Day) -> InputSource {
+        match (self.input, self.input_str) {
+            (_, Some(input)) => InputSource::Inline(input),
+            (Some(input_path), None) => InputSource::from_path(input_path),
+            (None, None) => day.default_input_source(),
+        }
+    }
 }
 
-fn run_part(part: &str, part_func: PartFn, input: &str) {
-    let (result, maybe_expected) = (part_func)(input);
-    match (result, maybe_expected) {
-        (value, Some(expected)) => {
-            if value == expected {
-                println!("✅ {}: {} (same as expected)", part, value)
+struct Runner {
+    reporter: Reporter,
+    record: bool,
+    answers: AnswersStore,
+    /// Whether new answers were recorded and need to be saved.
+    answers_changed: bool,
+    limits: Limits,
+    /// Results of the parts run on a readable input, for the history.
+    history: Vec<HistoryEntry>,
+}
+
+impl Runner {
+    /// Displays the outcome of the part, and records its answer if wanted.
+    fn report_part(
+        &mut self,
+        day: &dyn Day,
+        part: u8,
+        input_label: &str,
+        input: &str,
+        outcome: &PartOutcome,
+    ) {
+        self.reporter.part_done(day.name(), part, outcome);
+
+        if let (true, PartStatus::Unknown(value)) = (self.record, &outcome.status) {
+            self.answers
+                .record(day.name(), input_label, input, part, value.clone());
+            self.answers_changed = true;
+            let label = self.reporter.part_label(day.name(), part);
+            let message = format!("📝 {}: {} recorded as the expected answer", label, value);
+            // Do not break the output of other formats, they are for tools, not for humans.
+            if self.reporter.is_text() {
+                println!("{}", message);
             } else {
-                println!("❌ {}: Expected {} but got {} !!", part, expected, value);
+                eprintln!("{}", message);
             }
         }
-        (value, None) => eprintln!("-- {}: {} ?", part, value),
-    };
-}
+    }
 
-fn run_day_with_input_path(day: &Day, input_path: &Path) -> AnyResult<()> {
-    println!("=>> {} <<=", day.name);
-    let buf = std::fs::read_to_string(input_path)?;
-    run_part("Part1", day.part1, &buf);
-    run_part("Part2", day.part2, &buf);
-    Ok(())
+    /// Displays the outcomes of the day's parts, returns whether they are all ok.
+    fn report_day(
+        &mut self,
+        day: &dyn Day,
+        input_label: &str,
+        input: &ReadInput,
+        parts: &[(u8, Option<Answer>)],
+        outcomes: Vec<PartOutcome>,
+    ) -> bool {
+        self.reporter.day_start(day.name());
+        let buf = input.as_deref().unwrap_or_default();
+        let mut all_ok = true;
+        for (&(part, _), outcome) in parts.iter().zip(outcomes) {
+            all_ok &= outcome.status.is_ok();
+            self.report_part(day, part, input_label, buf, &outcome);
+            if input.is_ok() {
+                self.history
+                    .push(HistoryEntry::new(day.name(), part, buf, &outcome));
+            }
+        }
+        all_ok
+    }
+
+    /// Runs the selected parts of the day on its examples, returns whether they are all ok.
+    ///
+    /// NOTE: Only the parts with an expected answer are run, an example is made to check them.
+    fn run_examples(&mut self, day: &dyn Day, sel: &PartSelection) -> bool {
+        let examples = day.examples();
+        if examples.is_empty() && self.reporter.is_text() {
+            println!("No examples for {}", day.name());
+        }
+        let mut all_ok = true;
+        for (idx, example) in examples.iter().enumerate() {
+            let name = format!("{} example{}", day.name(), idx + 1);
+            let parts: Vec<_> = sel
+                .parts()
+                .into_iter()
+                .filter_map(|part| Some((part, Some(example.expected(part)?))))
+                .collect();
+            self.reporter.day_start(&name);
+            let input = Ok(input::dedent(example.input));
+            let outcomes = solve_input(day, &input, &parts, &self.limits, 1);
+            for (&(part, _), outcome) in parts.iter().zip(outcomes) {
+                all_ok &= outcome.status.is_ok();
+                self.reporter.part_done(&name, part, &outcome);
+            }
+        }
+        all_ok
+    }
+
+    /// Cross-checks the parts of the day that have a reference implementation, on generated
+    /// inputs of increasing size, returns whether they all give the same answer as their
+    /// reference.
+    ///
+    /// NOTE: It stops at the first input giving a different answer, the smallest one.
+    fn run_cross_checks(&mut self, day: &dyn Day) -> bool {
+        let mut rng = Rng::new(cross::SEED);
+        for size in cross::SIZES {
+            let Some(input) = day.generate_input(size, &mut rng) else {
+                return true;
+            };
+            let name = format!("{} cross{}", day.name(), size);
+            self.reporter.day_start(&name);
+            let mut all_ok = true;
+            for (part, outcome) in day.cross_check(&input) {
+                all_ok &= outcome.status.is_ok();
+                self.reporter.part_done(&name, part, &outcome);
+            }
+            if !all_ok {
+                if self.reporter.is_text() {
+                    println!("Generated input of {}:\n{}", name, input);
+                }
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Gives the selected parts, with their expected answer for the input.
+    fn parts_with_expected(
+        &self,
+        day: &dyn Day,
+        input: &ReadInput,
+        sel: &PartSelection,
+    ) -> Vec<(u8, Option<Answer>)> {
+        sel.parts()
+            .into_iter()
+            .map(|part| {
+                let expected = match input {
+                    Ok(buf) => self.answers.expected(day.name(), buf, part),
+                    Err(_) => None,
+                };
+                (part, expected)
+            })
+            .collect()
+    }
+
+    /// Runs the selected parts of the day and displays their outcomes, returns whether they are
+    /// all ok.
+    fn run_day_with_input(
+        &mut self,
+        day: &dyn Day,
+        input_source: &InputSource,
+        sel: &PartSelection,
+    ) -> bool {
+        let input = input_source.read();
+        let parts = self.parts_with_expected(day, &input, sel);
+        let outcomes = solve_input(day, &input, &parts, &self.limits, 1);
+        self.report_day(day, &input_source.label(), &input, &parts, outcomes)
+    }
+
+    /// Runs all parts of the given days, solving `jobs` days at the same time, and displays their
+    /// outcomes in the order of the days. Returns whether they are all ok.
+    ///
+    /// NOTE: With multiple jobs, both parts of a day are also solved at the same time (with the
+    ///       same parsed input).
+    fn run_days(&mut self, days: &[&dyn Day], jobs: usize) -> bool {
+        if jobs <= 1 {
+            // Display the outcomes as soon as they are known.
+            let mut all_ok = true;
+            for day in days {
+                all_ok &= self.run_day(*day, &PartSelection::all());
+            }
+            return all_ok;
+        }
+
+        let days_parts: Vec<_> = days
+            .iter()
+            .map(|&day| {
+                let input = day.default_input_source().read();
+                let parts = self.parts_with_expected(day, &input, &PartSelection::all());
+                (day, input, parts)
+            })
+            .collect();
+        let outcomes = parallel::map_with_jobs(&days_parts, jobs, |(day, input, parts)| {
+            solve_input(*day, input, parts, &self.limits, parts.len())
+        });
+
+        let mut all_ok = true;
+        for ((day, input, parts), outcomes) in days_parts.iter().zip(outcomes) {
+            let input_label = day.default_input_source().label();
+            all_ok &= self.report_day(*day, &input_label, input, parts, outcomes);
+        }
+        all_ok
+    }
+
+    fn run_day(&mut self, day: &dyn Day, sel: &PartSelection) -> bool {
+        self.run_day_with_input(day, &day.default_input_source(), sel)
+    }
+
+    /// Benches the day with the input, an unreadable input only fails this day.
+    fn bench_day(&self, day: &dyn Day, input_source: &InputSource, config: &BenchConfig) {
+        let buf = match input_source.read() {
+            Ok(buf) => buf,
+            Err(err) => {
+                println!("=>> {} <<=\n💥 {}", day.name(), err);
+                return;
+            }
+        };
+        let expected = [
+            self.answers.expected(day.name(), &buf, 1),
+            self.answers.expected(day.name(), &buf, 2),
+        ];
+        bench::bench_day(day, &buf, expected, config);
+    }
+
+    /// Solves the part on the day's input, and submits its answer when it is not known yet.
+    fn submit_part(&mut self, day: &dyn Day, part: u8, website: &WebsiteArgs) -> AnyResult<()> {
+        let input_source = day.default_input_source();
+        let input = input_source.read().map_err(anyhow::Error::msg)?;
+        if let Some(expected) = self.answers.expected(day.name(), &input, part) {
+            println!(
+                "{} part{} is already solved: {}",
+                day.name(),
+                part,
+                expected
+            );
+            return Ok(());
+        }
+        let outcome = solve_parts(day, &input, &[(part, None)]).remove(0);
+        let answer = match outcome.status {
+            PartStatus::Unknown(answer) => answer,
+            PartStatus::ParseError(err) | PartStatus::Failed(err) | PartStatus::Panicked(err) => {
+                bail!("{} part{} gives no answer:\n{}", day.name(), part, err)
+            }
+            _ => unreachable!("The part has no expected answer"),
+        };
+
+        let client = website.client()?;
+        println!("Submitting {} for {} part{}", answer, day.name(), part);
+        let answers = self
+            .answers
+            .input_answers_mut(day.name(), &input_source.label(), &input);
+        let verdict = submit::submit(&client, day.name(), answers, part, answer, time::now())?;
+        self.answers_changed = true;
+        println!("{}", verdict);
+        Ok(())
+    }
+
+    /// Runs the day silently, and returns the outcomes of all its parts.
+    fn solve_day(&self, day: &dyn Day) -> Vec<PartOutcome> {
+        let input = day.default_input_source().read();
+        let parts = self.parts_with_expected(day, &input, &PartSelection::all());
+        solve_input(day, &input, &parts, &self.limits, 1)
+    }
 }
 
-fn run_day(day: &Day) -> AnyResult<()> {
-    run_day_with_input_path(day, &PathBuf::from(day.default_input))
+/// Input of a day, or the error message when it cannot be read.
+type ReadInput = Result<String, String>;
+
+/// Solves the parts of the day with the input (`jobs` parts at the same time), or fails them all
+/// if the input cannot be read. With limits, each part is solved in a child process, one after
+/// the other.
+fn solve_input(
+    day: &dyn Day,
+    input: &ReadInput,
+    parts: &[(u8, Option<Answer>)],
+    limits: &Limits,
+    jobs: usize,
+) -> Vec<PartOutcome> {
+    let exe = std::env::current_exe();
+    match (input, exe) {
+        (Ok(buf), Ok(exe)) if limits.is_set() => {
+            isolate::solve_parts_isolated(&exe, day, buf, parts, limits)
+        }
+        (Ok(buf), _) => solve_parts_with_jobs(day, buf, parts, jobs),
+        (Err(err), _) => parts
+            .iter()
+            .map(|_| PartOutcome::failed(err.clone()))
+            .collect(),
+    }
 }
 
 fn main() -> anyhow::Result<()> {
-    // TODO: Use clap to parse params to structured opts!
-    let prog_args: Vec<String> = env::args().collect();
-    let first_arg = prog_args.get(1);
-
-    // NOTE: We convert Option<String> to Option<&str> to be able to match on `Some("all")`,
-    //       instead of matching `Some(xyz) if xyz == "all"` many times, which is quite ugly.
-    match first_arg.map(String::as_str) {
-        Some("all") => {
-            for day in DAYS {
-                run_day(day)?;
+    let cli = Cli::parse();
+    let mut runner = Runner {
+        reporter: Reporter::new(cli.format, cli.quiet),
+        record: cli.record,
+        answers: AnswersStore::load(&cli.answers)?,
+        answers_changed: false,
+        limits: Limits {
+            timeout: cli.timeout.map(Duration::from_secs_f64),
+            max_memory: cli.max_memory.map(|mib| mib * 1024 * 1024),
+        },
+        history: vec![],
+    };
+    let mut check_failed = false;
+
+    match cli.cmd {
+        Cmd::Run {
+            day,
+            sel,
+            input,
+            example,
+        } => {
+            if example {
+                runner.run_examples(day, &sel);
+            } else {
+                runner.run_day_with_input(day, &input.source(day), &sel);
             }
         }
-        Some("last") => {
-            run_day(&DAYS.last().unwrap())?;
+        Cmd::All { jobs } => {
+            runner.run_days(DAYS, parallel::effective_jobs(jobs));
+            runner.reporter.summary();
+        }
+        Cmd::Last { sel } => {
+            runner.run_day(*DAYS.last().unwrap(), &sel);
         }
-        Some("list") => {
+        Cmd::List => {
             println!("Available days:");
             for day in DAYS {
-                println!("- {}", day.name);
-                // TODO: Display how finish that day it, by running it without displaying anything
-                //       (need to return proper enum with all statuses)
+                let outcomes = runner.solve_day(*day);
+                let nb_solved = outcomes.iter().filter(|o| o.status.is_solved()).count();
+                let symbols: Vec<_> = outcomes.iter().map(|o| o.status.symbol()).collect();
+                println!(
+                    "- {} {} ({}/{} parts solved) {}",
+                    day.name(),
+                    symbols.join(" "),
+                    nb_solved,
+                    outcomes.len(),
+                    day.title()
+                );
             }
         }
-        Some(wanted_day) => {
-            let matching_day = DAYS.iter().find(|day| day.name == wanted_day);
-            match matching_day {
-                Some(day) => {
-                    let input_path = match prog_args.get(2) {
-                        Some(input_path) => PathBuf::from(input_path),
-                        None => PathBuf::from(day.default_input),
-                    };
-                    run_day_with_input_path(day, &input_path)?;
-                }
+        Cmd::Bench {
+            day,
+            runs,
+            warmup,
+            input,
+        } => {
+            let config = BenchConfig { runs, warmup };
+            match day {
+                Some(day) => runner.bench_day(day, &input.source(day), &config),
+                None if input.is_set() => bail!("A custom input can only be used to bench a day"),
                 None => {
-                    println!("Unknown day '{}'", wanted_day);
-                    exit(1);
+                    for day in DAYS {
+                        runner.bench_day(*day, &day.default_input_source(), &config);
+                    }
                 }
-            };
+            }
+        }
+        Cmd::Check { examples, cross } => {
+            runner.reporter.set_quiet(true);
+            let mut all_ok = true;
+            for day in DAYS {
+                if examples {
+                    all_ok &= runner.run_examples(*day, &PartSelection::all());
+                }
+                if cross {
+                    all_ok &= runner.run_cross_checks(*day);
+                }
+                all_ok &= runner.run_day(*day, &PartSelection::all());
+            }
+            if all_ok && !cli.quiet && runner.reporter.is_text() {
+                println!("All days give the expected answers");
+            }
+            check_failed = !all_ok;
+        }
+        Cmd::New { name, title } => {
+            scaffold::new_day(Path::new("."), &name, &title)?;
+            println!(
+                "Created src/{0}.rs & inputs/{0}.txt, {0} is available at the next build",
+                name
+            );
+        }
+        Cmd::Fetch { name, website } => {
+            let input_path = PathBuf::from(format!("./inputs/{}.txt", name));
+            let status = fetch::fetch_input(&input_path, || website.client()?.get_input(&name))?;
+            match status {
+                FetchStatus::Downloaded => println!("Downloaded {}", input_path.display()),
+                FetchStatus::Cached => println!("{} already exists", input_path.display()),
+            }
+        }
+        Cmd::Submit { day, part, website } => {
+            runner.submit_part(day, part, &website)?;
+        }
+        Cmd::History {
+            day,
+            sel,
+            count,
+            slowdown,
+        } => {
+            let entries = history::load(&cli.history)?;
+            for part in sel.parts() {
+                history::show_part(&entries, day.name(), part, count, slowdown);
+            }
+        }
+        Cmd::SolvePart {
+            day,
+            part,
+            max_memory,
+        } => {
+            isolate::solve_part_child(day, part, max_memory)?;
+        }
+        Cmd::Watch { day, interval } => {
+            watch::watch_day(day, &cli.answers, Duration::from_millis(interval))?;
         }
-        None => print_usage(),
     };
+
+    runner.reporter.finish();
+    if runner.answers_changed {
+        runner.answers.save(&cli.answers)?;
+    }
+    if !cli.no_history && !runner.history.is_empty() {
+        history::append_run(&cli.history, runner.history)?;
+    }
+    if check_failed {
+        exit(1);
+    }
     Ok(())
 }
@@ -0,0 +1,320 @@
+// Outcome of running a day's part
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::diagnostic::ParseError;
+use crate::panics::catch_panic;
+use crate::parallel;
+use crate::{Answer, Day, PartResult};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PartStatus {
+    /// The part gives the expected answer.
+    Solved(Answer),
+    /// The part gives an answer, but not the expected one.
+    Mismatch { expected: Answer, got: Answer },
+    /// The part gives an answer, but there is no known expected answer (yet).
+    Unknown(Answer),
+    /// The input could not be parsed, with the report of the parsing errors.
+    ParseError(String),
+    /// The input could not be parsed, as already reported with the given part (the report is
+    /// only given once per input).
+    ParseFailed { reported_in: u8 },
+    /// The part failed with an error.
+    Failed(String),
+    /// The part crashed while solving, with the panic message & where it happened.
+    Panicked(String),
+    /// The part took more time than allowed, and was stopped.
+    TimedOut,
+    /// The part needed more memory than allowed, and was stopped.
+    OutOfMemory,
+}
+
+impl PartStatus {
+    /// Whether the part can be considered done and right.
+    pub fn is_solved(&self) -> bool {
+        matches!(self, PartStatus::Solved(_))
+    }
+
+    /// Whether the part is not known to be wrong.
+    /// An `Unknown` answer is not wrong (yet)...
+    pub fn is_ok(&self) -> bool {
+        matches!(self, PartStatus::Solved(_) | PartStatus::Unknown(_))
+    }
+
+    /// Short name of the status, for tools consuming the results.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PartStatus::Solved(_) => "solved",
+            PartStatus::Mismatch { .. } => "mismatch",
+            PartStatus::Unknown(_) => "unknown",
+            PartStatus::ParseError(_) | PartStatus::ParseFailed { .. } => "parse_error",
+            PartStatus::Failed(_) => "failed",
+            PartStatus::Panicked(_) => "panicked",
+            PartStatus::TimedOut => "timed_out",
+            PartStatus::OutOfMemory => "out_of_memory",
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            PartStatus::Solved(_) => "✅",
+            PartStatus::Mismatch { .. } => "❌",
+            PartStatus::Unknown(_) => "--",
+            PartStatus::ParseError(_)
+            | PartStatus::ParseFailed { .. }
+            | PartStatus::Failed(_)
+            | PartStatus::Panicked(_) => "💥",
+            PartStatus::TimedOut => "⏰",
+            PartStatus::OutOfMemory => "🐘",
+        }
+    }
+
+    /// Checks the answer of an `Unknown` status against the expected one, if known.
+    pub fn with_expected(self, expected: Option<Answer>) -> Self {
+        match (self, expected) {
+            (PartStatus::Unknown(value), Some(expected)) if value == expected => {
+                PartStatus::Solved(value)
+            }
+            (PartStatus::Unknown(got), Some(expected)) => PartStatus::Mismatch { expected, got },
+            (status, _) => status,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartOutcome {
+    pub status: PartStatus,
+    pub duration: Duration,
+}
+
+impl PartOutcome {
+    /// Solves the part with the given solver, catching any panic of the solver.
+    /// The answer is checked against the expected one if known.
+    pub fn solve(solver: impl FnOnce() -> PartResult, expected: Option<Answer>) -> Self {
+        let start = Instant::now();
+        let result = catch_panic(solver);
+        let duration = start.elapsed();
+
+        let status = match result {
+            Ok(Ok(value)) => PartStatus::Unknown(value).with_expected(expected),
+            Ok(Err(err)) => error_status(err),
+            Err(report) => PartStatus::Panicked(report.to_string()),
+        };
+        Self { status, duration }
+    }
+
+    /// Outcome of a part that could not even be started (e.g. its input cannot be read).
+    pub fn failed(message: String) -> Self {
+        Self {
+            status: PartStatus::Failed(message),
+            duration: Duration::ZERO,
+        }
+    }
+
+    /// Displays the outcome with the given part label.
+    pub fn report(&self, part: &str) {
+        let symbol = self.status.symbol();
+        let took = format!("(took {:.2?})", self.duration);
+        match &self.status {
+            PartStatus::Solved(value) => println!(
+                "{} {}: {} (same as expected) {}",
+                symbol,
+                part,
+                fmt_answer(value),
+                took
+            ),
+            PartStatus::Mismatch { expected, got } => println!(
+                "{} {}: Expected {} but got {} !! {}",
+                symbol,
+                part,
+                fmt_answer(expected),
+                fmt_answer(got),
+                took
+            ),
+            PartStatus::Unknown(value) => {
+                eprintln!("{} {}: {} ? {}", symbol, part, fmt_answer(value), took)
+            }
+            PartStatus::ParseError(report) => {
+                println!("{} {}: Cannot parse the input:\n{}", symbol, part, report)
+            }
+            PartStatus::ParseFailed { reported_in } => println!(
+                "{} {}: Cannot parse the input (see Part{})",
+                symbol, part, reported_in
+            ),
+            PartStatus::Failed(err) => println!("{} {}: Failed: {}", symbol, part, err),
+            PartStatus::Panicked(msg) => println!("{} {}: Panicked: {}", symbol, part, msg),
+            PartStatus::TimedOut => println!("{} {}: Timed out {}", symbol, part, took),
+            PartStatus::OutOfMemory => println!("{} {}: Out of memory", symbol, part),
+        }
+    }
+}
+
+/// Parses the input once, then solves the given parts (with their expected answer) with it.
+///
+/// NOTE: When the input cannot be parsed, all the parts fail, see `parse_failed`.
+pub fn solve_parts(
+    day: &dyn Day,
+    raw_input: &str,
+    parts: &[(u8, Option<Answer>)],
+) -> Vec<PartOutcome> {
+    solve_parts_with_jobs(day, raw_input, parts, 1)
+}
+
+/// Like `solve_parts`, but solves (at most) `jobs` parts at the same time, all with the same
+/// parsed input.
+///
+/// NOTE: With a single job, the parts are solved one after the other on the current thread (the
+///       threads of `parallel` have a smaller stack than the main thread).
+pub fn solve_parts_with_jobs(
+    day: &dyn Day,
+    raw_input: &str,
+    parts: &[(u8, Option<Answer>)],
+    jobs: usize,
+) -> Vec<PartOutcome> {
+    let start = Instant::now();
+    let parsed = catch_panic(|| day.parse(raw_input));
+    let parse_duration = start.elapsed();
+
+    let status = match parsed {
+        Ok(Ok(parsed)) => {
+            let solve = |(part, expected): &(u8, Option<Answer>)| {
+                PartOutcome::solve(|| parsed.solve(*part), expected.clone())
+            };
+            if jobs <= 1 {
+                return parts.iter().map(solve).collect();
+            }
+            return parallel::map_with_jobs(parts, jobs, solve);
+        }
+        Ok(Err(err)) => error_status(err),
+        Err(report) => PartStatus::Panicked(report.to_string()),
+    };
+    let parts: Vec<_> = parts.iter().map(|(part, _)| *part).collect();
+    parse_failed(status, parse_duration, &parts)
+}
+
+/// Outcomes of the parts when their input cannot be parsed: the first part has the parsing
+/// error & time, the others point back to it, so the error is only reported once.
+pub(crate) fn parse_failed(
+    status: PartStatus,
+    parse_duration: Duration,
+    parts: &[u8],
+) -> Vec<PartOutcome> {
+    let Some(&first) = parts.first() else {
+        return vec![];
+    };
+    let mut outcomes = vec![PartOutcome {
+        status,
+        duration: parse_duration,
+    }];
+    outcomes.extend(parts[1..].iter().map(|_| PartOutcome {
+        status: PartStatus::ParseFailed { reported_in: first },
+        duration: Duration::ZERO,
+    }));
+    outcomes
+}
+
+/// Displays the answer on the same line, or on its own lines when it has multiple lines.
+fn fmt_answer(answer: &Answer) -> String {
+    if answer.is_multiline() {
+        format!("\n{}\n", answer)
+    } else {
+        answer.to_string()
+    }
+}
+
+pub(crate) fn error_status(err: anyhow::Error) -> PartStatus {
+    match err.downcast::<ParseError>() {
+        Ok(parse_err) => PartStatus::ParseError(parse_err.to_string()),
+        // NOTE: Alternate Display of anyhow's errors includes the causes.
+        Err(err) => PartStatus::Failed(format!("{:#}", err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Solution;
+
+    struct TestDay;
+
+    impl Solution for TestDay {
+        type Input = usize;
+        const NAME: &'static str = "test";
+        const TITLE: &'static str = "Test";
+
+        fn parse(raw_input: &str) -> anyhow::Result<usize> {
+            Ok(raw_input.parse()?)
+        }
+
+        fn part1(input: &usize) -> PartResult {
+            Ok((*input).into())
+        }
+
+        fn part2(_: &usize) -> PartResult {
+            panic!("crash with {}", "format")
+        }
+    }
+
+    #[test]
+    fn test_solve_statuses() {
+        let answer_42 = || Ok(Answer::Int(42));
+        assert_eq!(
+            PartOutcome::solve(answer_42, Some(Answer::Int(42))).status,
+            PartStatus::Solved(Answer::Int(42))
+        );
+        assert_eq!(
+            PartOutcome::solve(answer_42, Some(Answer::Int(41))).status,
+            PartStatus::Mismatch {
+                expected: Answer::Int(41),
+                got: Answer::Int(42)
+            }
+        );
+        assert_eq!(
+            PartOutcome::solve(answer_42, None).status,
+            PartStatus::Unknown(Answer::Int(42))
+        );
+    }
+
+    #[test]
+    fn test_solve_parts() {
+        let statuses: Vec<_> =
+            solve_parts(&TestDay, "42", &[(1, Some(Answer::Int(42))), (2, None)])
+                .into_iter()
+                .map(|outcome| outcome.status)
+                .collect();
+        assert_eq!(statuses[0], PartStatus::Solved(Answer::Int(42)));
+        // The other part still runs after a panic, which is reported with its location
+        assert!(
+            matches!(&statuses[1], PartStatus::Panicked(msg) if msg.starts_with("crash with format (at src/outcome.rs:")),
+            "{:?}",
+            statuses[1]
+        );
+
+        let outcomes = solve_parts(&TestDay, "nope", &[(1, None), (2, None)]);
+        assert_eq!(outcomes.len(), 2);
+        assert!(matches!(outcomes[0].status, PartStatus::Failed(_)));
+        // The error is only given once
+        let reported_in_part1 = PartStatus::ParseFailed { reported_in: 1 };
+        assert_eq!(outcomes[1].status, reported_in_part1);
+        assert_eq!(outcomes[1].duration, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_solve_parts_with_jobs() {
+        let parts = [(1, Some(Answer::Int(42))), (2, None)];
+        let statuses: Vec<_> = solve_parts_with_jobs(&TestDay, "42", &parts, 2)
+            .into_iter()
+            .map(|outcome| outcome.status)
+            .collect();
+        assert_eq!(statuses[0], PartStatus::Solved(Answer::Int(42)));
+        // The panic of a part solved in another thread is caught too
+        assert!(
+            matches!(&statuses[1], PartStatus::Panicked(msg) if msg.starts_with("crash with format")),
+            "{:?}",
+            statuses[1]
+        );
+    }
+}
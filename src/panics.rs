@@ -0,0 +1,99 @@
+// Catching the panics of the solvers, with where they happened
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+/// A caught panic: its message, and where it happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicReport {
+    pub message: String,
+    pub location: Option<String>,
+}
+
+impl fmt::Display for PanicReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let Some(location) = &self.location {
+            write!(f, " (at {})", location)?;
+        }
+        Ok(())
+    }
+}
+
+thread_local! {
+    /// Whether a panic of this thread is caught by `catch_panic`.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    /// Location of the last caught panic of this thread.
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// Installs a panic hook that keeps where the panic happened, instead of printing it (only for
+/// the panics caught by `catch_panic`, others are printed as usual).
+fn install_hook() {
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING.with(Cell::get) {
+                return default_hook(info);
+            }
+            // NOTE: The days' modules are included with their absolute path (see `build.rs`),
+            //       it is shorter to display it from the root of the project.
+            let location = info.location().map(|loc| {
+                let root = concat!(env!("CARGO_MANIFEST_DIR"), "/");
+                loc.to_string().trim_start_matches(root).to_string()
+            });
+            LAST_PANIC.with(|last| *last.borrow_mut() = location);
+        }));
+    });
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    // NOTE: `panic!("literal")` gives a `&str` payload, `panic!("{}", x)` gives a `String` one.
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "<unknown panic payload>".to_string()
+    }
+}
+
+/// Runs `func`, and catches its panic if it panics.
+///
+/// NOTE: The solvers do not share any state with us, so even if they panic midway there is
+///       nothing left in a broken state to worry about.
+pub fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, PanicReport> {
+    install_hook();
+    let was_catching = CATCHING.with(|catching| catching.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    CATCHING.with(|catching| catching.set(was_catching));
+
+    result.map_err(|payload| PanicReport {
+        message: panic_message(payload.as_ref()),
+        location: LAST_PANIC.with(|last| last.borrow_mut().take()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catch_panic() {
+        assert_eq!(catch_panic(|| 42), Ok(42));
+
+        let numbers: Vec<u32> = vec![];
+        let line = line!() + 1;
+        let report = catch_panic(|| numbers[numbers.len() / 2]).unwrap_err();
+        assert_eq!(
+            report.message,
+            "index out of bounds: the len is 0 but the index is 0"
+        );
+        let location = format!("src/panics.rs:{}:", line);
+        assert!(report.to_string().contains(&location), "{}", report);
+    }
+}
@@ -0,0 +1,76 @@
+// Minimal thread pool, to run things concurrently
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// Number of jobs to use when 0 is given: the number of CPUs.
+pub fn effective_jobs(jobs: usize) -> usize {
+    if jobs == 0 {
+        thread::available_parallelism().map_or(1, |n| n.get())
+    } else {
+        jobs
+    }
+}
+
+/// Applies `func` to all items using (at most) `jobs` threads, and returns the results in the same
+/// order as the items.
+///
+/// NOTE: The items are taken one by one by the threads as soon as they are free, so a slow item
+///       does not block the other threads.
+pub fn map_with_jobs<T, R, F>(items: &[T], jobs: usize, func: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next_idx = AtomicUsize::new(0);
+    let results: Vec<Mutex<Option<R>>> = items.iter().map(|_| Mutex::new(None)).collect();
+
+    // NOTE: Scoped threads can borrow from the current stack (items, results, func), because they
+    //       are all joined before the end of the scope.
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            scope.spawn(|| loop {
+                let idx = next_idx.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(idx) else {
+                    break;
+                };
+                let result = func(item);
+                *results[idx].lock().unwrap() = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_iter()
+        .map(|result| {
+            let result = result.into_inner().unwrap();
+            result.expect("All items are processed before the end of the scope")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_with_jobs_keeps_order() {
+        let items: Vec<u64> = (0..50).collect();
+        for jobs in [1, 4, 100] {
+            let results = map_with_jobs(&items, jobs, |n| {
+                // Make the first items slower, so they finish last.
+                thread::sleep(std::time::Duration::from_micros(50 - n));
+                n * 2
+            });
+            assert_eq!(results, items.iter().map(|n| n * 2).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_map_with_jobs_no_items() {
+        let results = map_with_jobs(&[] as &[u8], 4, |n| *n);
+        assert!(results.is_empty());
+    }
+}
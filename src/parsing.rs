@@ -0,0 +1,129 @@
+// Reusable parsers for the shapes of input that come back every day
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use chumsky as c;
+use chumsky::prelude::*;
+
+use crate::grid::{Grid, Pos};
+
+/// Unsigned integer of any type, like `42`.
+///
+/// NOTE: A number too big for the type is a parse error, instead of a panic.
+pub fn unsigned<N>() -> impl Parser<char, N, Error = Simple<char>> + Copy
+where
+    N: FromStr,
+    N::Err: Display,
+{
+    c::text::int(10)
+        // Q: Why do I need to specify `: String` for map?
+        // Answer from author: (ref: https://github.com/zesterer/chumsky/discussions/40#discussioncomment-1750744)
+        //   Many of the parsers in the text module are generic across Unicode (via char) and
+        //   ASCII (via u8) characters, including ident. The String is required to tell text::int
+        //   that it should be parsing chars and not u8s. You could also swap out String with Vec<u8>
+        //   if you wanted a parser that parses integers using ASCII bytes.
+        .try_map(|digits: String, span| parse_number(&digits, span))
+}
+
+/// Signed integer of any type, like `42`, `-42` or `+42`.
+pub fn signed<N>() -> impl Parser<char, N, Error = Simple<char>> + Copy
+where
+    N: FromStr,
+    N::Err: Display,
+{
+    just('-')
+        .or(just('+'))
+        .or_not()
+        .then(c::text::int(10))
+        .try_map(|(sign, digits): (Option<char>, String), span| {
+            let text: String = sign.into_iter().chain(digits.chars()).collect();
+            parse_number(&text, span)
+        })
+}
+
+fn parse_number<N>(text: &str, span: std::ops::Range<usize>) -> Result<N, Simple<char>>
+where
+    N: FromStr,
+    N::Err: Display,
+{
+    text.parse()
+        .map_err(|err| Simple::custom(span, format!("invalid number {}: {}", text, err)))
+}
+
+/// Items separated by commas, like `1,2,3`.
+pub fn comma_list<T>(
+    item: impl Parser<char, T, Error = Simple<char>>,
+) -> impl Parser<char, Vec<T>, Error = Simple<char>> {
+    item.separated_by(just(',')).at_least(1)
+}
+
+/// Records, one per line (at least one).
+pub fn lines<T>(
+    record: impl Parser<char, T, Error = Simple<char>>,
+) -> impl Parser<char, Vec<T>, Error = Simple<char>> {
+    record.separated_by(c::text::newline()).at_least(1)
+}
+
+/// An empty line, between two blocks of lines.
+pub fn blank_line() -> impl Parser<char, (), Error = Simple<char>> + Copy {
+    c::text::newline().then(c::text::newline()).ignored()
+}
+
+/// Blocks of lines, separated by an empty line (at least one).
+pub fn blocks<T>(
+    block: impl Parser<char, T, Error = Simple<char>>,
+) -> impl Parser<char, Vec<T>, Error = Simple<char>> {
+    block.separated_by(blank_line()).at_least(1)
+}
+
+/// Block of digits, one row per line, like:
+///   2199943210
+///   3987894921
+pub fn digit_grid() -> impl Parser<char, Grid<u32>, Error = Simple<char>> {
+    let digit = one_of("0123456789").map(|chr: char| chr.to_digit(10).unwrap());
+    lines(digit.repeated().at_least(1))
+        .try_map(|rows, span| Grid::from_rows(rows).map_err(|err| Simple::custom(span, err)))
+}
+
+/// Position like `x,y`.
+pub fn point() -> impl Parser<char, Pos, Error = Simple<char>> + Copy {
+    signed()
+        .then_ignore(just(','))
+        .then(signed())
+        .map(Pos::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostic;
+
+    #[test]
+    fn test_numbers() {
+        let numbers: Vec<u8> = diagnostic::parse(lines(unsigned()), "1\n255\n").unwrap();
+        assert_eq!(numbers, vec![1, 255]);
+        let err = diagnostic::parse(lines(unsigned::<u8>()), "1\n256").unwrap_err();
+        assert!(err.to_string().contains("invalid number 256"), "{}", err);
+        assert!(diagnostic::parse(unsigned::<u32>(), "-1").is_err());
+        assert!(diagnostic::parse(lines(unsigned::<u32>()), "\n").is_err());
+
+        let numbers: Vec<i64> = diagnostic::parse(comma_list(signed()), "-3,+4,5").unwrap();
+        assert_eq!(numbers, vec![-3, 4, 5]);
+        let point = diagnostic::parse(point(), "-1,2").unwrap();
+        assert_eq!(point, Pos::new(-1, 2));
+    }
+
+    #[test]
+    fn test_blocks_and_grid() {
+        let parser = blocks(lines(comma_list(unsigned::<u32>())));
+        let blocks = diagnostic::parse(parser, "1,2\n3\n\n4").unwrap();
+        assert_eq!(blocks, vec![vec![vec![1, 2], vec![3]], vec![vec![4]]]);
+
+        let grid = diagnostic::parse(digit_grid(), "12\n34\n").unwrap();
+        assert_eq!(grid[Pos::new(1, 1)], 4);
+        let err = diagnostic::parse(digit_grid(), "12\n3").unwrap_err();
+        assert!(err.to_string().contains("Row 1 has 1 cells"), "{}", err);
+        assert!(diagnostic::parse(digit_grid(), "1a").is_err());
+    }
+}
@@ -0,0 +1,285 @@
+// Display of the runs' outcomes, for humans or for tools
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::outcome::{PartOutcome, PartStatus};
+use crate::Answer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable lines
+    Text,
+    /// A single JSON array of all part results, at the end
+    Json,
+    /// One JSON object per part result, as soon as it is known
+    Jsonl,
+    /// Test Anything Protocol (v13)
+    Tap,
+}
+
+/// Result of a part, in a form that is easy to consume by other tools.
+#[derive(Debug, Clone, Serialize)]
+pub struct PartRecord {
+    pub day: String,
+    pub part: u8,
+    pub status: &'static str,
+    pub value: Option<Answer>,
+    pub expected: Option<Answer>,
+    pub error: Option<String>,
+    pub duration_ms: f64,
+}
+
+impl PartRecord {
+    pub fn new(day_name: &str, part: u8, outcome: &PartOutcome) -> Self {
+        let (value, expected, error) = match &outcome.status {
+            PartStatus::Solved(value) => (Some(value.clone()), Some(value.clone()), None),
+            PartStatus::Mismatch { expected, got } => {
+                (Some(got.clone()), Some(expected.clone()), None)
+            }
+            PartStatus::Unknown(value) => (Some(value.clone()), None, None),
+            PartStatus::ParseError(err) | PartStatus::Failed(err) | PartStatus::Panicked(err) => {
+                (None, None, Some(err.clone()))
+            }
+            PartStatus::ParseFailed { reported_in } => {
+                (None, None, Some(format!("see part{}", reported_in)))
+            }
+            PartStatus::TimedOut | PartStatus::OutOfMemory => (None, None, None),
+        };
+        Self {
+            day: day_name.to_string(),
+            part,
+            status: outcome.status.name(),
+            value,
+            expected,
+            error,
+            duration_ms: outcome.duration.as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+/// Displays the outcomes of the parts as they are run, in the wanted format.
+pub struct Reporter {
+    format: OutputFormat,
+    quiet: bool,
+    /// Records kept until the end, for formats that cannot be streamed.
+    records: Vec<PartRecord>,
+    nb_tap_tests: usize,
+    /// Number of parts done for each status, with the status' symbol.
+    status_counts: BTreeMap<&'static str, (&'static str, usize)>,
+}
+
+impl Reporter {
+    pub fn new(format: OutputFormat, quiet: bool) -> Self {
+        Self {
+            format,
+            quiet,
+            records: vec![],
+            nb_tap_tests: 0,
+            status_counts: BTreeMap::new(),
+        }
+    }
+
+    pub fn set_quiet(&mut self, quiet: bool) {
+        self.quiet = quiet;
+    }
+
+    pub fn is_text(&self) -> bool {
+        self.format == OutputFormat::Text
+    }
+
+    pub fn day_start(&mut self, day_name: &str) {
+        if self.is_text() && !self.quiet {
+            println!("=>> {} <<=", day_name);
+        }
+    }
+
+    /// Returns the label of the part, as displayed to humans.
+    pub fn part_label(&self, day_name: &str, part: u8) -> String {
+        // NOTE: Without the day header, the day name must be in the part label to know which day
+        //       is failing.
+        if self.quiet {
+            format!("{} Part{}", day_name, part)
+        } else {
+            format!("Part{}", part)
+        }
+    }
+
+    pub fn part_done(&mut self, day_name: &str, part: u8, outcome: &PartOutcome) {
+        let count = self
+            .status_counts
+            .entry(outcome.status.name())
+            .or_insert((outcome.status.symbol(), 0));
+        count.1 += 1;
+
+        match self.format {
+            OutputFormat::Text => {
+                if !self.quiet || !outcome.status.is_solved() {
+                    outcome.report(&self.part_label(day_name, part));
+                }
+            }
+            OutputFormat::Json => self.records.push(PartRecord::new(day_name, part, outcome)),
+            OutputFormat::Jsonl => {
+                let record = PartRecord::new(day_name, part, outcome);
+                println!("{}", serde_json::to_string(&record).unwrap());
+            }
+            OutputFormat::Tap => {
+                if self.nb_tap_tests == 0 {
+                    println!("TAP version 13");
+                }
+                self.nb_tap_tests += 1;
+                let record = PartRecord::new(day_name, part, outcome);
+                println!("{}", tap_test_line(self.nb_tap_tests, &record));
+            }
+        }
+    }
+
+    /// Displays how many parts were done for each status (only for humans, tools can count).
+    pub fn summary(&self) {
+        if self.is_text() {
+            if !self.quiet {
+                println!("=>> Summary <<=");
+            }
+            println!("{}", summary_line(&self.status_counts));
+        }
+    }
+
+    /// Displays what is left to display, once all parts were run.
+    pub fn finish(&mut self) {
+        match self.format {
+            OutputFormat::Text | OutputFormat::Jsonl => {}
+            OutputFormat::Json => {
+                println!("{}", serde_json::to_string_pretty(&self.records).unwrap());
+            }
+            OutputFormat::Tap => {
+                if self.nb_tap_tests == 0 {
+                    println!("TAP version 13");
+                }
+                // NOTE: TAP allows the plan at the end, when the number of tests is not known in
+                //       advance.
+                println!("1..{}", self.nb_tap_tests);
+            }
+        }
+    }
+}
+
+// Gives a line like:
+//   20 parts: 1 ❌ mismatch, 17 ✅ solved, 2 -- unknown
+fn summary_line(status_counts: &BTreeMap<&'static str, (&'static str, usize)>) -> String {
+    let total: usize = status_counts.values().map(|(_, count)| count).sum();
+    let counts: Vec<_> = status_counts
+        .iter()
+        .map(|(name, (symbol, count))| format!("{} {} {}", count, symbol, name))
+        .collect();
+    if counts.is_empty() {
+        "0 parts".to_string()
+    } else {
+        format!("{} parts: {}", total, counts.join(", "))
+    }
+}
+
+// Gives lines like:
+//   ok 1 - day01 part1: 1502
+//   ok 2 - day01 part2: 1538 # SKIP no known answer
+//   not ok 3 - day02 part1: mismatch
+//     ---
+//     value: 42
+//     expected: 43
+//     ...
+fn tap_test_line(test_num: usize, record: &PartRecord) -> String {
+    let description = format!("{} part{}", record.day, record.part);
+    match record.status {
+        "solved" => format!(
+            "ok {} - {}: {}",
+            test_num,
+            description,
+            tap_inline_value(&record.value)
+        ),
+        "unknown" => format!(
+            "ok {} - {}: {} # SKIP no known answer",
+            test_num,
+            description,
+            tap_inline_value(&record.value)
+        ),
+        status => {
+            let mut lines = vec![format!("not ok {} - {}: {}", test_num, description, status)];
+            lines.push("  ---".to_string());
+            if let Some(value) = &record.value {
+                lines.extend(yaml_field("value", &value.to_string()));
+            }
+            if let Some(expected) = &record.expected {
+                lines.extend(yaml_field("expected", &expected.to_string()));
+            }
+            if let Some(error) = &record.error {
+                lines.extend(yaml_field("error", error));
+            }
+            lines.push("  ...".to_string());
+            lines.join("\n")
+        }
+    }
+}
+
+/// The answer on a single line, for the test line.
+fn tap_inline_value(value: &Option<Answer>) -> String {
+    match value {
+        Some(value) => value.to_string().replace('\n', "\\n"),
+        None => String::new(),
+    }
+}
+
+/// Field of the YAML block of a test, a multi-line content is written as an indented block.
+fn yaml_field(name: &str, content: &str) -> Vec<String> {
+    if content.contains('\n') {
+        let mut lines = vec![format!("  {}: |", name)];
+        lines.extend(content.lines().map(|line| format!("    {}", line)));
+        lines
+    } else {
+        vec![format!("  {}: {}", name, content)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(status: &'static str, value: Option<i64>, expected: Option<i64>) -> PartRecord {
+        PartRecord {
+            day: "day01".to_string(),
+            part: 2,
+            status,
+            value: value.map(Answer::Int),
+            expected: expected.map(Answer::Int),
+            error: None,
+            duration_ms: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_summary_line() {
+        let mut status_counts = BTreeMap::new();
+        assert_eq!(summary_line(&status_counts), "0 parts");
+        status_counts.insert("solved", ("✅", 17));
+        status_counts.insert("mismatch", ("❌", 1));
+        assert_eq!(
+            summary_line(&status_counts),
+            "18 parts: 1 ❌ mismatch, 17 ✅ solved"
+        );
+    }
+
+    #[test]
+    fn test_tap_test_line() {
+        assert_eq!(
+            tap_test_line(1, &record("solved", Some(42), Some(42))),
+            "ok 1 - day01 part2: 42"
+        );
+        assert_eq!(
+            tap_test_line(2, &record("unknown", Some(42), None)),
+            "ok 2 - day01 part2: 42 # SKIP no known answer"
+        );
+        assert_eq!(
+            tap_test_line(3, &record("mismatch", Some(42), Some(43))),
+            "not ok 3 - day01 part2: mismatch\n  ---\n  value: 42\n  expected: 43\n  ..."
+        );
+    }
+}
@@ -0,0 +1,109 @@
+// Scaffolding of a new day, from the `day_example.rs` template
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result as AnyResult};
+
+static TEMPLATE: &str = include_str!("day_example.rs");
+
+/// Checks the day name is like `day11`, and returns its number.
+pub(crate) fn day_number(name: &str) -> AnyResult<u8> {
+    let number = name
+        .strip_prefix("day")
+        .filter(|num| num.len() == 2)
+        .and_then(|num| num.parse::<u8>().ok())
+        .filter(|num| (1..=25).contains(num));
+    match number {
+        Some(number) => Ok(number),
+        None => bail!(
+            "Invalid day name '{}', expected dayNN (from day01 to day25)",
+            name
+        ),
+    }
+}
+
+/// Gives the source of the new day's module, from the template.
+fn render_template(name: &str, title: &str) -> String {
+    let number = &name["day".len()..];
+    TEMPLATE
+        .replace("// Day not_a_day", &format!("// day {}", number))
+        .replace("DayNN", &format!("Day{}", number))
+        .replace("dayNN", name)
+        // NOTE: Debug repr of a string gives a valid Rust string literal (with quotes & escapes).
+        .replace("\"TODO\"", &format!("{:?}", title))
+}
+
+/// Creates the new day's module & (empty) input, in the project at `root`.
+///
+/// NOTE: There is nothing else to do, the build script finds the new day's module.
+pub fn new_day(root: &Path, name: &str, title: &str) -> AnyResult<()> {
+    day_number(name)?;
+    let src_path = root.join("src").join(format!("{}.rs", name));
+    let input_path = root.join("inputs").join(format!("{}.txt", name));
+    if src_path.exists() {
+        bail!("{} already exists", src_path.display());
+    }
+
+    std::fs::write(&src_path, render_template(name, title))
+        .with_context(|| format!("Cannot write {}", src_path.display()))?;
+    // NOTE: Do not overwrite an input that was already downloaded.
+    if !input_path.exists() {
+        std::fs::write(&input_path, "")
+            .with_context(|| format!("Cannot write {}", input_path.display()))?;
+    }
+    Ok(())
+}
+
+// NOTE: The template is compiled in the tests, like the new days will be, so that a broken
+//       template is seen before a day is created from it.
+#[cfg(test)]
+#[path = "day_example.rs"]
+mod template;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::dedent;
+    use crate::outcome::{solve_parts, PartStatus};
+    use crate::Solution;
+
+    #[test]
+    fn test_render_template() {
+        assert!(day_number("day1").is_err());
+        assert!(day_number("day26").is_err());
+        assert_eq!(day_number("day11").unwrap(), 11);
+
+        let source = render_template("day11", "Dumbo \"Octopus\"");
+        assert!(source.starts_with("// day 11\n"));
+        assert!(source.contains("pub struct Day11;"));
+        assert!(source.contains("const NAME: &'static str = \"day11\";"));
+        assert!(source.contains("const TITLE: &'static str = \"Dumbo \\\"Octopus\\\"\";"));
+        assert!(!source.contains("NN"));
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-test-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("inputs")).unwrap();
+
+        new_day(&root, "day11", "Dumbo Octopus").unwrap();
+        let source = std::fs::read_to_string(root.join("src/day11.rs")).unwrap();
+        assert_eq!(source, render_template("day11", "Dumbo Octopus"));
+        assert!(root.join("inputs/day11.txt").exists());
+        assert!(new_day(&root, "day11", "Again").is_err());
+        std::fs::remove_dir_all(&root).unwrap();
+
+        // A new day parses its example, and solves both parts without knowing their answers
+        for example in template::DayNN::examples() {
+            let parts = [(1, example.part1), (2, example.part2)];
+            for outcome in solve_parts(&template::DayNN, &dedent(example.input), &parts) {
+                assert!(
+                    matches!(outcome.status, PartStatus::Unknown(_)),
+                    "{:?}",
+                    outcome
+                );
+            }
+        }
+    }
+}
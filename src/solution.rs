@@ -0,0 +1,166 @@
+// Common interface of the days' solutions
+
+use std::path::PathBuf;
+
+use anyhow::Result as AnyResult;
+
+use crate::cross::{self, Rng};
+use crate::input::InputSource;
+use crate::outcome::PartOutcome;
+use crate::{Answer, PartResult};
+
+/// Solution of a day: how to parse its input, and how to solve both parts from the parsed input.
+///
+/// NOTE: The input is parsed once and shared by both parts, instead of being parsed again in
+///       every part.
+pub trait Solution {
+    /// Input of the day, once parsed.
+    ///
+    /// NOTE: It is `Sync` so both parts can be solved at the same time with the same input.
+    type Input: Sync + 'static;
+
+    /// Name of the day, like `day01` (also used to find its input file).
+    const NAME: &'static str;
+    /// Title of the day's puzzle, as given on the website.
+    const TITLE: &'static str;
+
+    fn parse(raw_input: &str) -> AnyResult<Self::Input>;
+    fn part1(input: &Self::Input) -> PartResult;
+    fn part2(input: &Self::Input) -> PartResult;
+
+    /// Examples given in the puzzle, with their expected answers.
+    fn examples() -> Vec<Example> {
+        vec![]
+    }
+
+    /// Slow but simple implementations of some parts (by part), to cross-check the real ones.
+    fn references() -> Vec<(u8, Reference<Self::Input>)> {
+        vec![]
+    }
+
+    /// Random well-formed input of about `size` items, to cross-check the parts with their
+    /// references (`None` when the day can't generate inputs).
+    fn generate_input(_size: usize, _rng: &mut Rng) -> Option<String> {
+        None
+    }
+}
+
+/// Slow but simple implementation of a part, solving it from the parsed input.
+pub type Reference<I> = fn(&I) -> PartResult;
+
+/// An example of the puzzle, with the answers it gives (when the puzzle says so).
+#[derive(Debug, Clone)]
+pub struct Example {
+    /// Input of the example, its indentation is removed (see `input::dedent`).
+    pub input: &'static str,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Example {
+    pub fn new(input: &'static str, part1: impl Into<Answer>, part2: impl Into<Answer>) -> Self {
+        Self {
+            input,
+            part1: Some(part1.into()),
+            part2: Some(part2.into()),
+        }
+    }
+
+    pub fn expected(&self, part: u8) -> Option<Answer> {
+        match part {
+            1 => self.part1.clone(),
+            2 => self.part2.clone(),
+            _ => None,
+        }
+    }
+}
+
+/// A day's input once parsed, ready to solve parts with it (from multiple threads).
+pub trait ParsedInput: Sync {
+    fn solve(&self, part: u8) -> PartResult;
+}
+
+struct Parsed<S: Solution>(S::Input);
+
+impl<S: Solution> ParsedInput for Parsed<S> {
+    fn solve(&self, part: u8) -> PartResult {
+        solve_part::<S>(part, &self.0)
+    }
+}
+
+/// Solves a part (1 or 2) of the solution with the parsed input.
+pub(crate) fn solve_part<S: Solution>(part: u8, input: &S::Input) -> PartResult {
+    match part {
+        1 => S::part1(input),
+        2 => S::part2(input),
+        _ => unreachable!("There are only 2 parts in a day"),
+    }
+}
+
+/// Object-safe version of `Solution`, to have all days (with different inputs) in a registry.
+///
+/// NOTE: It is implemented for all solutions, there is no need to implement it by hand.
+pub trait Day: Sync {
+    fn name(&self) -> &'static str;
+    fn title(&self) -> &'static str;
+    fn parse(&self, raw_input: &str) -> AnyResult<Box<dyn ParsedInput>>;
+    fn examples(&self) -> Vec<Example>;
+    fn generate_input(&self, size: usize, rng: &mut Rng) -> Option<String>;
+    /// Solves the parts that have a reference on the input, see `cross::cross_check`.
+    fn cross_check(&self, raw_input: &str) -> Vec<(u8, PartOutcome)>;
+
+    fn default_input(&self) -> PathBuf {
+        PathBuf::from(format!("./inputs/{}.txt", self.name()))
+    }
+
+    fn default_input_source(&self) -> InputSource {
+        InputSource::File(self.default_input())
+    }
+}
+
+impl<S: Solution + Sync + 'static> Day for S {
+    fn name(&self) -> &'static str {
+        S::NAME
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn parse(&self, raw_input: &str) -> AnyResult<Box<dyn ParsedInput>> {
+        let input = <S as Solution>::parse(raw_input)?;
+        Ok(Box::new(Parsed::<S>(input)))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        S::examples()
+    }
+
+    fn generate_input(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        S::generate_input(size, rng)
+    }
+
+    fn cross_check(&self, raw_input: &str) -> Vec<(u8, PartOutcome)> {
+        cross::cross_check::<S>(raw_input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::dedent;
+    use crate::outcome::solve_parts;
+    use crate::DAYS;
+
+    #[test]
+    fn test_all_examples() {
+        for day in DAYS {
+            for example in day.examples() {
+                let parts = [(1, example.part1.clone()), (2, example.part2.clone())];
+                let parts: Vec<_> = parts.into_iter().filter(|(_, e)| e.is_some()).collect();
+                for outcome in solve_parts(*day, &dedent(example.input), &parts) {
+                    assert!(outcome.status.is_solved(), "{}: {:?}", day.name(), outcome);
+                }
+            }
+        }
+    }
+}
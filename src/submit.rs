@@ -0,0 +1,247 @@
+// Submission of the answers to the website, with the bookkeeping of wrong answers & cooldowns
+
+use std::fmt;
+
+use anyhow::{bail, Result as AnyResult};
+
+use crate::answers::{Hint, InputAnswers, WrongAnswer};
+use crate::fetch::AocClient;
+use crate::Answer;
+
+/// What the website said about a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    /// With the time to wait before the next submission, in seconds.
+    Wrong {
+        hint: Option<Hint>,
+        wait: u64,
+    },
+    /// An answer was submitted too recently, nothing was checked.
+    Wait(u64),
+    /// The part is already solved (or not unlocked yet), nothing was checked.
+    WrongLevel,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Right => write!(f, "✅ That's the right answer!"),
+            Verdict::Wrong { hint, wait } => {
+                let hint = match hint {
+                    Some(Hint::TooHigh) => ", too high",
+                    Some(Hint::TooLow) => ", too low",
+                    None => "",
+                };
+                write!(
+                    f,
+                    "❌ Wrong answer{} (wait {}s to submit again)",
+                    hint, wait
+                )
+            }
+            Verdict::Wait(wait) => write!(f, "⏳ Too soon, wait {}s to submit again", wait),
+            Verdict::WrongLevel => write!(f, "🤔 The part is already solved, or not unlocked yet"),
+        }
+    }
+}
+
+fn unit_seconds(unit: char) -> Option<u64> {
+    match unit {
+        'h' => Some(3600),
+        'm' => Some(60),
+        's' => Some(1),
+        _ => None,
+    }
+}
+
+/// Reads a duration like `one minute`, `5 minutes` or `1m 25s`, in seconds.
+fn parse_duration(text: &str) -> Option<u64> {
+    let words: Vec<_> = text.split_whitespace().collect();
+    let mut total = 0;
+    for (idx, word) in words.iter().enumerate() {
+        // Like `1m` or `25s`
+        if let Some(unit) = word.chars().last().and_then(unit_seconds) {
+            if let Ok(num) = word[..word.len() - 1].parse::<u64>() {
+                total += num * unit;
+                continue;
+            }
+        }
+        // Like `one minute` or `5 minutes`
+        let num = match *word {
+            "one" => Some(1),
+            _ => word.parse::<u64>().ok(),
+        };
+        let unit = words.get(idx + 1).and_then(|w| w.chars().next());
+        if let (Some(num), Some(unit)) = (num, unit.and_then(unit_seconds)) {
+            total += num * unit;
+        }
+    }
+    (total > 0).then_some(total)
+}
+
+/// Gives the text of `page` between `start` and `end`.
+fn text_between<'a>(page: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let rest = &page[page.find(start)? + start.len()..];
+    Some(&rest[..rest.find(end)?])
+}
+
+/// Reads the verdict from the page given by the website after a submission.
+pub fn parse_response(page: &str) -> AnyResult<Verdict> {
+    let page = &page.to_lowercase();
+    if page.contains("that's the right answer") {
+        Ok(Verdict::Right)
+    } else if page.contains("that's not the right answer") {
+        let hint = if page.contains("your answer is too high") {
+            Some(Hint::TooHigh)
+        } else if page.contains("your answer is too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        // NOTE: The website asks to wait a minute after a wrong answer, and more after a few.
+        let wait = text_between(page, "please wait ", "before trying again")
+            .and_then(parse_duration)
+            .unwrap_or(60);
+        Ok(Verdict::Wrong { hint, wait })
+    } else if page.contains("you gave an answer too recently") {
+        let wait = text_between(page, "you have ", "left to wait")
+            .and_then(parse_duration)
+            .unwrap_or(60);
+        Ok(Verdict::Wait(wait))
+    } else if page.contains("you don't seem to be solving the right level") {
+        Ok(Verdict::WrongLevel)
+    } else {
+        bail!("Cannot find the verdict in the response of the website")
+    }
+}
+
+/// Checks the answer can be submitted: not during a cooldown, and not known to be wrong.
+pub fn check_submission(
+    answers: &InputAnswers,
+    part: u8,
+    answer: &Answer,
+    now: u64,
+) -> Result<(), String> {
+    if let Some(wait_until) = answers.wait_until.filter(|&t| t > now) {
+        return Err(format!("wait {}s to submit again", wait_until - now));
+    }
+    let value = answer.to_string().parse::<i128>().ok();
+    for wrong in answers.wrong.iter().filter(|w| w.part == part) {
+        if &wrong.answer == answer {
+            return Err(format!("{} was already submitted, and is wrong", answer));
+        }
+        let bound = wrong.answer.to_string().parse::<i128>().ok();
+        match (wrong.hint, value, bound) {
+            (Some(Hint::TooHigh), Some(value), Some(bound)) if value >= bound => {
+                return Err(format!("{} is too high, {} already was", value, bound));
+            }
+            (Some(Hint::TooLow), Some(value), Some(bound)) if value <= bound => {
+                return Err(format!("{} is too low, {} already was", value, bound));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Records what the website said about the answer.
+pub fn record_verdict(
+    answers: &mut InputAnswers,
+    part: u8,
+    answer: Answer,
+    verdict: &Verdict,
+    now: u64,
+) {
+    match verdict {
+        Verdict::Right => {
+            *answers.part_mut(part) = Some(answer);
+            answers.wait_until = None;
+        }
+        Verdict::Wrong { hint, wait } => {
+            answers.wrong.push(WrongAnswer {
+                part,
+                answer,
+                hint: *hint,
+            });
+            answers.wait_until = Some(now + wait);
+        }
+        Verdict::Wait(wait) => answers.wait_until = Some(now + wait),
+        Verdict::WrongLevel => {}
+    }
+}
+
+/// Submits the answer of the part (when it can be), and records the verdict of the website.
+pub fn submit(
+    client: &AocClient,
+    day_name: &str,
+    answers: &mut InputAnswers,
+    part: u8,
+    answer: Answer,
+    now: u64,
+) -> AnyResult<Verdict> {
+    // NOTE: A multi-line answer is usually letters drawn in a grid, only a human can read them.
+    if answer.is_multiline() {
+        bail!(
+            "Cannot submit a multi-line answer, read it and submit it by hand:\n{}",
+            answer
+        );
+    }
+    if let Err(reason) = check_submission(answers, part, &answer, now) {
+        bail!("Not submitted: {}", reason);
+    }
+    let page = client.post_answer(day_name, part, &answer.to_string())?;
+    let verdict = parse_response(&page)?;
+    record_verdict(answers, part, answer, &verdict, now);
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_response() {
+        let page = "<article><p>That's not the right answer; your answer is too high. \
+                    Please wait one minute before trying again.</p></article>";
+        let hint = Some(Hint::TooHigh);
+        assert_eq!(
+            parse_response(page).unwrap(),
+            Verdict::Wrong { hint, wait: 60 }
+        );
+        let page = "That's not the right answer. please wait 5 minutes before trying again.";
+        let wrong = Verdict::Wrong {
+            hint: None,
+            wait: 300,
+        };
+        assert_eq!(parse_response(page).unwrap(), wrong);
+        let page = "You gave an answer too recently; you have to wait after submitting an \
+                    answer before trying again.  You have 1m 25s left to wait.";
+        assert_eq!(parse_response(page).unwrap(), Verdict::Wait(85));
+        let page = "<p>That's the right answer!  You are one gold star closer.</p>";
+        assert_eq!(parse_response(page).unwrap(), Verdict::Right);
+        let page = "You don't seem to be solving the right level.  Did you already complete it?";
+        assert_eq!(parse_response(page).unwrap(), Verdict::WrongLevel);
+        assert!(parse_response("<html>Not found</html>").is_err());
+    }
+
+    #[test]
+    fn test_check_submission() {
+        let mut answers = InputAnswers::default();
+        let wrong = Verdict::Wrong {
+            hint: Some(Hint::TooHigh),
+            wait: 60,
+        };
+        record_verdict(&mut answers, 1, Answer::Int(100), &wrong, 1000);
+        // During the cooldown
+        assert!(check_submission(&answers, 1, &Answer::Int(50), 1059).is_err());
+        // Known wrong, or higher than an answer too high
+        assert!(check_submission(&answers, 1, &Answer::Int(100), 1060).is_err());
+        assert!(check_submission(&answers, 1, &Answer::Int(120), 1060).is_err());
+        assert!(check_submission(&answers, 1, &Answer::Int(50), 1060).is_ok());
+        assert!(check_submission(&answers, 2, &Answer::Int(120), 1060).is_ok());
+
+        record_verdict(&mut answers, 1, Answer::Int(50), &Verdict::Right, 1060);
+        assert_eq!(answers.part1, Some(Answer::Int(50)));
+        assert_eq!(answers.wait_until, None);
+    }
+}
@@ -0,0 +1,11 @@
+// Wall-clock time, as stored in the answers & the history
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Current time, in seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,160 @@
+// Watch mode: re-runs a day when its source or its input changes
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result as AnyResult};
+use serde::Deserialize;
+
+use crate::{Answer, Day};
+
+/// Result of a part, as given by `run --format jsonl`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct PartLine {
+    part: u8,
+    status: String,
+    value: Option<Answer>,
+    error: Option<String>,
+}
+
+impl PartLine {
+    fn short(&self) -> String {
+        match &self.value {
+            Some(value) => format!("{} {}", value, self.status),
+            None => self.status.clone(),
+        }
+    }
+}
+
+/// Describes the result of a part compared to its previous result, like:
+///   Part1: 42 solved (was 41 mismatch)
+fn diff_line(previous: Option<&PartLine>, current: &PartLine) -> String {
+    let change = match previous {
+        None => "new".to_string(),
+        Some(previous) if previous == current => "unchanged".to_string(),
+        Some(previous) => format!("was {}", previous.short()),
+    };
+    let mut line = format!("Part{}: {} ({})", current.part, current.short(), change);
+    if let Some(error) = &current.error {
+        line += &format!("\n{}", error);
+    }
+    line
+}
+
+fn modified_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    // NOTE: A missing file has no time, so it is seen as changed when it is created.
+    paths
+        .iter()
+        .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+/// Runs the example tests of the day, then the day on its real input, both with cargo so that
+/// changes of the source are compiled.
+///
+/// NOTE: It runs on every save, so the slow property tests (`prop_*`) are skipped, and the runs
+///       are not added to the history.
+fn run_once(day: &dyn Day, answers_path: &Path) -> AnyResult<Option<Vec<PartLine>>> {
+    // NOTE: The tests of a day are in the `dayNN::tests` module, the filter selects them.
+    let tests_status = Command::new("cargo")
+        .args(["test", "--quiet", &format!("{}::", day.name())])
+        .args(["--", "--skip", "prop_"])
+        .status()
+        .context("Cannot run cargo test")?;
+    if !tests_status.success() {
+        println!("❌ Example tests failed");
+    }
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--",
+            "run",
+            day.name(),
+            "--format",
+            "jsonl",
+            "--no-history",
+        ])
+        .arg("--answers")
+        .arg(answers_path)
+        .output()
+        .context("Cannot run cargo run")?;
+    if !output.status.success() {
+        println!(
+            "💥 Cannot run the day:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        return Ok(None);
+    }
+    let lines = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    Ok(Some(lines))
+}
+
+/// Polls the source & input of the day, and re-runs it on every change (until killed).
+pub fn watch_day(day: &dyn Day, answers_path: &Path, interval: Duration) -> AnyResult<()> {
+    let paths = [
+        PathBuf::from(format!("./src/{}.rs", day.name())),
+        day.default_input(),
+    ];
+    println!(
+        "Watching {} & {} (Ctrl-C to stop)",
+        paths[0].display(),
+        paths[1].display()
+    );
+
+    let mut last_times = None;
+    let mut previous: Vec<PartLine> = vec![];
+    loop {
+        let times = modified_times(&paths);
+        if last_times.as_ref() != Some(&times) {
+            last_times = Some(times);
+            println!("=>> {} <<=", day.name());
+            if let Some(current) = run_once(day, answers_path)? {
+                for part_line in &current {
+                    let prev = previous.iter().find(|p| p.part == part_line.part);
+                    println!("{}", diff_line(prev, part_line));
+                }
+                previous = current;
+            }
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part_line(status: &str, value: Option<i64>) -> PartLine {
+        PartLine {
+            part: 1,
+            status: status.to_string(),
+            value: value.map(Answer::Int),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_line() {
+        let solved = part_line("solved", Some(42));
+        let mismatch = part_line("mismatch", Some(41));
+        assert_eq!(diff_line(None, &solved), "Part1: 42 solved (new)");
+        assert_eq!(
+            diff_line(Some(&solved), &solved),
+            "Part1: 42 solved (unchanged)"
+        );
+        assert_eq!(
+            diff_line(Some(&mismatch), &solved),
+            "Part1: 42 solved (was 41 mismatch)"
+        );
+        assert_eq!(
+            diff_line(Some(&solved), &part_line("panicked", None)),
+            "Part1: panicked (was 42 solved)"
+        );
+    }
+}